@@ -0,0 +1,87 @@
+// Generated from the JSON schema of the refined format. Do not edit by hand.
+
+export interface Group {
+    details: Detail[];
+    simplified: string;
+    simplified_stroke_count?: number;
+}
+
+export interface Classifier {
+    reference_id?: string;
+    simplified: string;
+    traditional: string;
+    wade_giles_pinyin: string;
+}
+
+export interface Decomposition {
+    graphical: string[];
+    ids?: string;
+    radical: Radical[];
+}
+
+export interface Detail {
+    classifiers?: Classifier[];
+    decomposition?: Decomposition;
+    examples?: Example[];
+    id: string;
+    meanings: Meaning[];
+    pronunciation: Pronunciation[];
+    similar?: string[];
+    simplified: string;
+    simplified_stroke_count?: number;
+    strokes?: Stroke[];
+    tags?: string[];
+    traditional: string;
+    traditional_stroke_count?: number;
+    variant?: Variant;
+}
+
+export interface Example {
+    id: number;
+    translations: Translation[];
+    value: string;
+}
+
+export interface Meaning {
+    context?: string[];
+    language?: string;
+    lexical_item?: string;
+    literal_meaning?: string;
+    pinyin?: string;
+    reference_id?: string;
+    simplified?: string;
+    traditional?: string;
+    value?: string;
+    wade_giles_pinyin?: string;
+}
+
+export interface Pronunciation {
+    jyutping?: string[];
+    other?: string;
+    pinyin: string;
+    wade_giles_pinyin: string;
+}
+
+export interface Radical {
+    meaning: string;
+    pinyin: string;
+    stroke_count: number;
+    value: string;
+}
+
+export interface Stroke {
+    median: number[][];
+    path: string;
+}
+
+export interface Translation {
+    language: string;
+    value: string;
+}
+
+export interface Variant {
+    reference_id?: string;
+    simplified: string;
+    traditional?: string;
+    wade_giles_pinyin?: string;
+}
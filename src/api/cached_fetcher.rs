@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::api::fetcher::Fetcher;
+use crate::api::fetcher::Page;
+use crate::utils::get_hash::get_hash;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    url: String,
+    body: Option<String>,
+}
+
+/// Keeps every response of the wrapped fetcher on disk, keyed by the url and
+/// the form data, so that an interrupted scrape doesn't request a page twice.
+/// Missing pages are cached as well, errors are not.
+pub struct CachedFetcher<F: Fetcher> {
+    pub fetcher: F,
+    pub directory: PathBuf,
+}
+
+impl<F: Fetcher> CachedFetcher<F> {
+    pub fn new(fetcher: F, directory: PathBuf) -> Self {
+        Self { fetcher, directory }
+    }
+
+    fn get_path(&self, page: &Page) -> PathBuf {
+        let mut key = page.url.to_owned();
+
+        if let Some(form) = &page.form {
+            let mut fields: Vec<String> = form
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            fields.sort();
+            key = format!("{} {}", key, fields.join("&"));
+        }
+
+        self.directory.join(format!("{:016x}.json", get_hash(&key)))
+    }
+}
+
+impl<F: Fetcher> Fetcher for CachedFetcher<F> {
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+        let file_path = self.get_path(page);
+
+        if file_path.exists() {
+            match serde_json::from_str::<CachedResponse>(&fs::read_to_string(&file_path)?) {
+                Ok(cached) if cached.url == page.url => return Ok(cached.body),
+                Ok(_) => {}
+                Err(error) => warn!("Ignoring unreadable {:?}: {}", file_path, error),
+            }
+        }
+
+        let body = self.fetcher.fetch(page)?;
+
+        fs::create_dir_all(&self.directory)?;
+        let cached = CachedResponse {
+            url: page.url.to_owned(),
+            body,
+        };
+        // Written aside and renamed, so a crash can't leave a truncated entry
+        let partial_path = file_path.with_extension("json.partial");
+        let mut buffer_writer = BufWriter::new(File::create(&partial_path)?);
+        serde_json::to_writer(&mut buffer_writer, &cached)?;
+        buffer_writer.flush()?;
+        drop(buffer_writer);
+        fs::rename(partial_path, file_path)?;
+
+        Ok(cached.body)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use std::env;
+
+    struct CountingFetcher {
+        count: Cell<usize>,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+            self.count.set(self.count.get() + 1);
+
+            match page.key.as_str() {
+                "error" => Err("connection reset".into()),
+                "missing" => Ok(None),
+                _ => Ok(Some(format!("<p>{}</p>", page.key))),
+            }
+        }
+    }
+
+    fn get_page(key: &str) -> Page {
+        Page {
+            source: "test",
+            key: key.to_string(),
+            extension: "html",
+            url: format!("https://example.com/{}", key),
+            form: None,
+        }
+    }
+
+    #[test]
+    fn should_cache_responses() {
+        let directory = env::temp_dir().join("refined-cedict-json-http-cache");
+        let _ = fs::remove_dir_all(&directory);
+
+        let fetcher = CachedFetcher::new(
+            CountingFetcher {
+                count: Cell::new(0),
+            },
+            directory.clone(),
+        );
+
+        for _ in 0..2 {
+            assert_eq!(
+                fetcher.fetch(&get_page("要")).unwrap(),
+                Some("<p>要</p>".to_string())
+            );
+            assert_eq!(fetcher.fetch(&get_page("missing")).unwrap(), None);
+        }
+        assert_eq!(fetcher.fetcher.count.get(), 2);
+
+        assert!(fetcher.fetch(&get_page("error")).is_err());
+        assert!(fetcher.fetch(&get_page("error")).is_err());
+        assert_eq!(fetcher.fetcher.count.get(), 4);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn should_fetch_again_after_truncated_entry() {
+        let directory = env::temp_dir().join("refined-cedict-json-http-cache-truncated");
+        let _ = fs::remove_dir_all(&directory);
+
+        let fetcher = CachedFetcher::new(
+            CountingFetcher {
+                count: Cell::new(0),
+            },
+            directory.clone(),
+        );
+        let page = get_page("要");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(fetcher.get_path(&page), r#"{"url":"https://exa"#).unwrap();
+
+        assert_eq!(fetcher.fetch(&page).unwrap(), Some("<p>要</p>".to_string()));
+        assert_eq!(fetcher.fetch(&page).unwrap(), Some("<p>要</p>".to_string()));
+        assert_eq!(fetcher.fetcher.count.get(), 1);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
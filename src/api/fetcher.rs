@@ -0,0 +1,156 @@
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::http_client::HTTP_CLIENT;
+
+/// A page of one of the scraped sites. `source` and `key` name the page in a
+/// fixture directory, `url` and `form` describe the live request.
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub source: &'static str,
+    pub key: String,
+    pub extension: &'static str,
+    pub url: String,
+    pub form: Option<HashMap<&'static str, String>>,
+}
+
+pub trait Fetcher {
+    /// The body of the page, or `None` when the site doesn't have it.
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+impl Fetcher for Box<dyn Fetcher> {
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+        self.as_ref().fetch(page)
+    }
+}
+
+/// Saved pages from the directory in `FIXTURES_DIRECTORY` when it is set, so
+/// the scraping commands can be run offline, and the live sites otherwise.
+/// With `CAPTURE_FIXTURES_DIRECTORY` set, live pages are also saved there in
+/// the fixture layout.
+pub fn get_fetcher() -> Box<dyn Fetcher> {
+    if let Some(directory) = env::var_os("FIXTURES_DIRECTORY") {
+        return Box::new(FixtureFetcher::new(PathBuf::from(directory)));
+    }
+
+    match env::var_os("CAPTURE_FIXTURES_DIRECTORY") {
+        Some(directory) => Box::new(CapturingFetcher {
+            fetcher: HttpFetcher,
+            fixtures: FixtureFetcher::new(PathBuf::from(directory)),
+        }),
+        None => Box::new(HttpFetcher),
+    }
+}
+
+pub struct HttpFetcher;
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+        let response = HTTP_CLIENT.send(|client| match &page.form {
+            Some(form) => client.post(&page.url).form(form),
+            None => client.get(&page.url),
+        })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        // A blocked or rejected request must not be cached as the page
+        let response = response.error_for_status()?;
+
+        Ok(Some(response.text()?))
+    }
+}
+
+/// Reads pages saved as `<directory>/<source>/<key>.<extension>`.
+pub struct FixtureFetcher {
+    pub directory: PathBuf,
+}
+
+impl FixtureFetcher {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    pub fn get_path(&self, page: &Page) -> PathBuf {
+        self.directory
+            .join(page.source)
+            .join(format!("{}.{}", page.key, page.extension))
+    }
+}
+
+impl Fetcher for FixtureFetcher {
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+        let file_path = self.get_path(page);
+
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(fs::read_to_string(file_path)?))
+    }
+}
+
+/// Saves every page `fetcher` returns where `fixtures` will find it.
+pub struct CapturingFetcher<F: Fetcher> {
+    pub fetcher: F,
+    pub fixtures: FixtureFetcher,
+}
+
+impl<F: Fetcher> Fetcher for CapturingFetcher<F> {
+    fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+        let body = self.fetcher.fetch(page)?;
+
+        if let Some(body) = &body {
+            let file_path = self.fixtures.get_path(page);
+
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(file_path, body)?;
+        }
+
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct StaticFetcher;
+
+    impl Fetcher for StaticFetcher {
+        fn fetch(&self, page: &Page) -> Result<Option<String>, Box<dyn Error>> {
+            Ok(Some(format!("<html>{}</html>", page.key)))
+        }
+    }
+
+    #[test]
+    fn should_capture_pages_as_fixtures() {
+        let directory = env::temp_dir().join("refined-cedict-json-capture");
+        let fetcher = CapturingFetcher {
+            fetcher: StaticFetcher,
+            fixtures: FixtureFetcher::new(directory.clone()),
+        };
+        let page = Page {
+            source: "strokeorder",
+            key: "要".to_string(),
+            extension: "html",
+            url: "http://localhost/".to_string(),
+            form: None,
+        };
+
+        fetcher.fetch(&page).unwrap();
+        let saved = FixtureFetcher::new(directory.clone()).fetch(&page).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(saved.as_deref(), Some("<html>要</html>"));
+    }
+}
@@ -0,0 +1,193 @@
+use lazy_static::lazy_static;
+use reqwest::blocking::Client;
+use reqwest::blocking::RequestBuilder;
+use reqwest::blocking::Response;
+use reqwest::StatusCode;
+use std::env;
+use std::error::Error;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+pub static DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+lazy_static! {
+    pub static ref HTTP_CLIENT: HttpClient = HttpClient::new(HttpSettings::from_env());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpSettings {
+    pub user_agent: String,
+    pub requests_per_second: f64,
+    pub timeout: Duration,
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            requests_per_second: 1.0,
+            timeout: Duration::from_secs(30),
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+fn get_env<T: std::str::FromStr>(key: &str) -> Option<T> {
+    env::var(key)
+        .ok()
+        .and_then(|pr| pr.trim().parse::<T>().ok())
+}
+
+impl HttpSettings {
+    /// Defaults overridden by `HTTP_USER_AGENT`, `HTTP_REQUESTS_PER_SECOND`,
+    /// `HTTP_TIMEOUT_SECONDS` and `HTTP_MAX_RETRIES`.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        Self {
+            user_agent: get_env("HTTP_USER_AGENT").unwrap_or(default.user_agent),
+            requests_per_second: get_env("HTTP_REQUESTS_PER_SECOND")
+                .filter(|pr: &f64| *pr > 0.0)
+                .unwrap_or(default.requests_per_second),
+            timeout: get_env("HTTP_TIMEOUT_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+            max_retries: get_env("HTTP_MAX_RETRIES").unwrap_or(default.max_retries),
+            initial_backoff: default.initial_backoff,
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.requests_per_second)
+    }
+
+    /// Doubles with every attempt, unless the server said how long to wait.
+    fn get_backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after.unwrap_or_else(|| self.initial_backoff * 2u32.saturating_pow(attempt))
+    }
+}
+
+fn should_retry(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn get_retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// The client every scraper goes through. Requests are spaced out to the
+/// configured rate and retried on timeouts, 429 and 5xx responses.
+pub struct HttpClient {
+    client: Client,
+    settings: HttpSettings,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl HttpClient {
+    pub fn new(settings: HttpSettings) -> Self {
+        let client = Client::builder()
+            .user_agent(settings.user_agent.to_owned())
+            .timeout(settings.timeout)
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            settings,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+
+        if let Some(last) = *last_request {
+            let interval = self.settings.get_interval();
+            let elapsed = last.elapsed();
+
+            if elapsed < interval {
+                thread::sleep(interval - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+
+    /// Sends the request built by `build`, which is called again for every
+    /// retry. Statuses other than 429 and 5xx are returned to the caller.
+    pub fn send<B>(&self, build: B) -> Result<Response, Box<dyn Error>>
+    where
+        B: Fn(&Client) -> RequestBuilder,
+    {
+        let mut attempt = 0;
+
+        loop {
+            self.wait_for_turn();
+
+            let (retry_after, error): (Option<Duration>, Box<dyn Error>) =
+                match build(&self.client).send() {
+                    Ok(response) if should_retry(response.status()) => (
+                        get_retry_after(&response),
+                        format!("{} responded with {}", response.url(), response.status()).into(),
+                    ),
+                    Ok(response) => return Ok(response),
+                    Err(error) if error.is_timeout() || error.is_connect() => (None, error.into()),
+                    Err(error) => return Err(error.into()),
+                };
+
+            if attempt >= self.settings.max_retries {
+                return Err(error);
+            }
+
+            let backoff = self.settings.get_backoff(attempt, retry_after);
+            warn!("{}, retrying in {:?}", error, backoff);
+            thread::sleep(backoff);
+            attempt = attempt + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_back_off_exponentially() {
+        let settings = HttpSettings::default();
+
+        assert_eq!(settings.get_backoff(0, None), Duration::from_secs(1));
+        assert_eq!(settings.get_backoff(3, None), Duration::from_secs(8));
+        assert_eq!(
+            settings.get_backoff(3, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+
+        let settings = HttpSettings {
+            requests_per_second: 4.0,
+            ..HttpSettings::default()
+        };
+        assert_eq!(settings.get_interval(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn should_retry_rate_limits_and_server_errors() {
+        assert!(should_retry(StatusCode::TOO_MANY_REQUESTS));
+        assert!(should_retry(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!should_retry(StatusCode::NOT_FOUND));
+        assert!(!should_retry(StatusCode::OK));
+    }
+}
@@ -1,207 +1,48 @@
 use bytes::Bytes;
-use lazy_static::lazy_static;
-use regex::Regex;
-use reqwest::StatusCode;
-use scraper::ElementRef;
-use scraper::Html;
-use scraper::Selector;
-use serde_json::Value;
-use std::collections::HashMap;
 use std::error::Error;
-use urlencoding::encode;
-pub mod get_character_decomposition_from_hanzicraft;
-pub mod get_radicals_from_wikipedia;
-use get_character_decomposition_from_hanzicraft::get_character_decomposition_from_hanzicraft;
-use get_radicals_from_wikipedia::get_radicals_from_wikipedia;
-
-use crate::models::Radical;
-
-pub static USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/93.0.4577.82 Safari/537.36";
+pub mod cached_fetcher;
+pub mod fetcher;
+pub mod http_client;
+pub mod parse_decomposition_from_hanzicraft;
+pub mod parse_pinyin_from_writtenchinese;
+pub mod parse_stroke_count_from_nihongo;
+pub mod parse_stroke_count_from_strokeorder;
+pub mod parse_stroke_count_from_wiktionary;
+pub mod providers;
+pub mod stroke_count_resolver;
+use fetcher::get_fetcher;
+use http_client::HTTP_CLIENT;
+use providers::*;
 
 pub fn download_cedict() -> Result<Bytes, Box<dyn Error>> {
-    lazy_static! {
-        static ref CLIENT: reqwest::blocking::Client =
-            reqwest::blocking::Client::builder().build().unwrap();
-    }
-
     const URL: &str = "https://www.mdbg.net/chinese/export/cedict/cedict_1_0_ts_utf-8_mdbg.zip";
-    let response = CLIENT
-        .get(URL)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()?;
+    let response = HTTP_CLIENT.send(|client| client.get(URL))?;
 
-    let bytes = response.bytes()?;
+    let bytes = response.error_for_status()?.bytes()?;
 
     Ok(bytes)
 }
 
 pub fn get_info_from_writtenchinese(pinyin: &str) -> Result<Option<String>, Box<dyn Error>> {
-    lazy_static! {
-        static ref CLIENT: reqwest::blocking::Client =
-            reqwest::blocking::Client::builder().build().unwrap();
-    }
-
-    let mut form_data = HashMap::new();
-    form_data.insert("searchKey", pinyin);
-
-    const url: &str = "https://dictionary.writtenchinese.com/ajaxsearch/simsearch.action";
-    let response = CLIENT
-        .post(url)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .form(&form_data)
-        .send()?;
-
-    if response.status() == StatusCode::NOT_FOUND {
-        return Ok(None);
-    }
-
-    let data = &response.bytes()?;
-
-    let value: Value = serde_json::from_slice(data)?;
-    let mut result: Option<String> = None;
-
-    match value.get("signPinyinShort") {
-        Some(records) => {
-            if !records.is_null() {
-                let record = records.as_array().unwrap().first().unwrap();
-                result = record["tone"].as_str().map(|pr| pr.to_owned());
-            }
-        }
-        None => {}
-    }
+    let provider = Writtenchinese {
+        fetcher: get_fetcher(),
+    };
 
-    if result.is_none() {
-        match value.get("mdbgPinyin") {
-            Some(records) => {
-                if !records.is_null() {
-                    let record = records.as_array().unwrap().first().unwrap();
-                    result = record["tone"].as_str().map(|pr| pr.to_owned());
-                }
-            }
-            None => {}
-        }
-    }
-
-    Ok(result)
+    provider.get_pinyin(pinyin)
 }
 
-pub fn get_stroke_count_from_wiktionary(character: &str) -> Result<Option<u8>, Box<dyn Error>> {
-    lazy_static! {
-        static ref REGEX: Regex = Regex::new(r"(\d+)\sstrokes?",).unwrap();
-        static ref CLIENT: reqwest::blocking::Client =
-            reqwest::blocking::Client::builder().build().unwrap();
-    }
-
-    let encoded = encode(character);
-    const BASE_URL: &str = "https://en.wiktionary.org/wiki/";
-    let url = format!("{}{}", BASE_URL, encoded);
-
-    let response: reqwest::blocking::Response = CLIENT
-        .get(url)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()?;
-
-    let body_response = response.text()?;
-
-    let captures = REGEX.captures(&body_response);
+pub fn get_stroke_count_from_nihongo(character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+    let provider = Nihongo {
+        fetcher: get_fetcher(),
+    };
 
-    if captures.is_none() {
-        return Ok(None);
-    }
-
-    let temp = captures.unwrap();
-    let mut iter = temp.iter();
-    iter.next();
-    let stroke_count_str = iter.next().unwrap().unwrap().as_str();
-    let stroke_count = stroke_count_str.parse::<u8>()?;
-
-    Ok(Some(stroke_count))
+    provider.get_stroke_count(character)
 }
 
-pub fn get_stroke_count_from_nihongo(character: &str) -> Result<u8, Box<dyn Error>> {
-    lazy_static! {
-        static ref CLIENT: reqwest::blocking::Client =
-            reqwest::blocking::Client::builder().build().unwrap();
-    }
-
-    let encoded = encode(character);
-    let base_url = "https://www.nihongo-pro.com/kanji-pal/kanji/";
-    let url = format!("{}{}", base_url, encoded);
-
-    let response: reqwest::blocking::Response = CLIENT
-        .get(url)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()
-        .unwrap();
-
-    let body_response = response.text()?;
-    let parsed_html = Html::parse_document(&body_response);
-
-    let selector = &Selector::parse(".container b").unwrap();
-    let mut stroke_count = 0;
-
-    for element in parsed_html.select(&selector) {
-        let text: String = element.text().collect();
-        if text.contains("Strokes：") {
-            let mut next_sibling = element.next_sibling().unwrap();
-            let mut stroke_count_str = next_sibling.value().as_text().unwrap().to_string();
-
-            let is_numeric = stroke_count_str.chars().all(char::is_numeric);
-
-            if !is_numeric {
-                next_sibling = next_sibling.next_sibling().unwrap();
-                stroke_count_str = next_sibling.value().as_text().unwrap().to_string();
-            }
-
-            stroke_count_str = stroke_count_str.trim().to_string();
-            stroke_count = stroke_count_str.parse::<u8>()?;
-            break;
-        }
-    }
-
-    Ok(stroke_count)
-}
-
-pub fn get_stroke_count_from_strokeorder(character: &str) -> Result<u8, Box<dyn Error>> {
-    lazy_static! {
-        static ref CLIENT: reqwest::blocking::Client =
-            reqwest::blocking::Client::builder().build().unwrap();
-    }
-
-    let encoded = encode(character);
-    let base_url = "http://www.strokeorder.info/mandarin.php?q=";
-    let url = format!("{}{}", base_url, encoded);
-
-    let response: reqwest::blocking::Response = CLIENT
-        .get(url)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()
-        .unwrap();
-
-    let body_response = response.text()?;
-    let parsed_html = Html::parse_document(&body_response);
-
-    let selector = &Selector::parse(".container b").unwrap();
-    let mut stroke_count = 0;
-
-    for element in parsed_html.select(&selector) {
-        let text: String = element.text().collect();
-        if text.contains("Strokes：") {
-            let mut next_sibling = element.next_sibling().unwrap();
-            let mut stroke_count_str = next_sibling.value().as_text().unwrap().to_string();
-
-            let is_numeric = stroke_count_str.chars().all(char::is_numeric);
-
-            if !is_numeric {
-                next_sibling = next_sibling.next_sibling().unwrap();
-                stroke_count_str = next_sibling.value().as_text().unwrap().to_string();
-            }
-
-            stroke_count_str = stroke_count_str.trim().to_string();
-            stroke_count = stroke_count_str.parse::<u8>()?;
-            break;
-        }
-    }
+pub fn get_stroke_count_from_strokeorder(character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+    let provider = Strokeorder {
+        fetcher: get_fetcher(),
+    };
 
-    Ok(stroke_count)
+    provider.get_stroke_count(character)
 }
@@ -0,0 +1,48 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::Html;
+use scraper::Selector;
+
+/// Joins the character with the once, radical and graphical breakdowns of a
+/// hanzicraft character page, in the layout of character-decomposition.txt.
+pub fn parse_decomposition_from_hanzicraft(character: &str, html: &str) -> String {
+    lazy_static! {
+        static ref ARROW_PATTERN: Regex = Regex::new(r"(?:.*\s=>\s)(.*)").unwrap();
+        static ref SELECTOR: Selector = Selector::parse(".decompbox").unwrap();
+    }
+
+    let parsed_html = Html::parse_document(html);
+    let mut parts: Vec<String> = vec![character.to_owned()];
+
+    for element in parsed_html.select(&SELECTOR) {
+        let text: String = element.text().collect();
+
+        if let Some(captures) = ARROW_PATTERN.captures(&text) {
+            parts.push(captures[1].trim().to_owned());
+        }
+    }
+
+    parts.join(";")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_parse_decomposition() {
+        let file_path = env::current_dir()
+            .unwrap()
+            .join("assets/fixtures/hanzicraft/要.html");
+        let html = fs::read_to_string(file_path).unwrap();
+
+        let line = parse_decomposition_from_hanzicraft("要", &html);
+        assert_eq!(line, "要;覀, 女;覀 (west), 女 (woman);覀, ㇛, 一, 丿");
+        assert_eq!(
+            parse_decomposition_from_hanzicraft("一", "<html></html>"),
+            "一"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use serde_json::Value;
+use std::error::Error;
+
+fn get_first_tone(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)?
+        .as_array()?
+        .first()?
+        .get("tone")?
+        .as_str()
+        .map(|pr| pr.to_owned())
+}
+
+/// Picks the accented pinyin out of a writtenchinese search response,
+/// preferring their own records over the ones taken from MDBG.
+pub fn parse_pinyin_from_writtenchinese(body: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let value: Value = serde_json::from_str(body)?;
+
+    Ok(get_first_tone(&value, "signPinyinShort").or_else(|| get_first_tone(&value, "mdbgPinyin")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_parse_pinyin() {
+        let file_path = env::current_dir()
+            .unwrap()
+            .join("assets/fixtures/writtenchinese/ren2.json");
+        let body = fs::read_to_string(file_path).unwrap();
+
+        assert_eq!(
+            parse_pinyin_from_writtenchinese(&body).unwrap(),
+            Some("rén".to_string())
+        );
+        assert_eq!(
+            parse_pinyin_from_writtenchinese(
+                r#"{"signPinyinShort":null,"mdbgPinyin":[{"tone":"rén"}]}"#
+            )
+            .unwrap(),
+            Some("rén".to_string())
+        );
+        assert_eq!(parse_pinyin_from_writtenchinese("{}").unwrap(), None);
+        assert!(parse_pinyin_from_writtenchinese("<html>").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use lazy_static::lazy_static;
+use scraper::Html;
+use scraper::Selector;
+use std::error::Error;
+
+/// Reads the number following the "Strokes：" label.
+pub fn parse_stroke_count_from_nihongo(html: &str) -> Result<Option<u8>, Box<dyn Error>> {
+    lazy_static! {
+        static ref SELECTOR: Selector = Selector::parse(".container b").unwrap();
+    }
+
+    let parsed_html = Html::parse_document(html);
+
+    for element in parsed_html.select(&SELECTOR) {
+        let text: String = element.text().collect();
+
+        if !text.contains("Strokes：") {
+            continue;
+        }
+
+        let stroke_count_str = element
+            .next_siblings()
+            .filter_map(|pr| pr.value().as_text())
+            .map(|pr| pr.trim())
+            .find(|pr| !pr.is_empty());
+
+        return match stroke_count_str {
+            Some(stroke_count_str) => Ok(Some(stroke_count_str.parse::<u8>()?)),
+            None => Ok(None),
+        };
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_parse_stroke_count() {
+        let file_path = env::current_dir()
+            .unwrap()
+            .join("assets/fixtures/nihongo/要.html");
+        let html = fs::read_to_string(file_path).unwrap();
+
+        assert_eq!(parse_stroke_count_from_nihongo(&html).unwrap(), Some(9));
+        assert_eq!(parse_stroke_count_from_nihongo("").unwrap(), None);
+        assert!(parse_stroke_count_from_nihongo(
+            r#"<div class="container"><b>Strokes：</b>many</div>"#
+        )
+        .is_err());
+    }
+}
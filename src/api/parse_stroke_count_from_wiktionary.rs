@@ -0,0 +1,32 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::error::Error;
+
+pub fn parse_stroke_count_from_wiktionary(html: &str) -> Result<Option<u8>, Box<dyn Error>> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"(\d+)\sstrokes?",).unwrap();
+    }
+
+    match REGEX.captures(html) {
+        Some(captures) => Ok(Some(captures[1].parse::<u8>()?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_parse_stroke_count() {
+        let file_path = env::current_dir()
+            .unwrap()
+            .join("assets/fixtures/wiktionary/要.html");
+        let html = fs::read_to_string(file_path).unwrap();
+
+        assert_eq!(parse_stroke_count_from_wiktionary(&html).unwrap(), Some(9));
+        assert_eq!(parse_stroke_count_from_wiktionary("").unwrap(), None);
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::error::Error;
+use urlencoding::encode;
+
+use crate::api::fetcher::Fetcher;
+use crate::api::fetcher::Page;
+use crate::api::parse_decomposition_from_hanzicraft::parse_decomposition_from_hanzicraft;
+use crate::api::parse_pinyin_from_writtenchinese::parse_pinyin_from_writtenchinese;
+use crate::api::parse_stroke_count_from_nihongo::parse_stroke_count_from_nihongo;
+use crate::api::parse_stroke_count_from_strokeorder::parse_stroke_count_from_strokeorder;
+use crate::api::parse_stroke_count_from_wiktionary::parse_stroke_count_from_wiktionary;
+use crate::models::UnihanRecord;
+
+pub trait StrokeCountProvider {
+    fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>>;
+}
+
+pub trait DecompositionProvider {
+    /// A line in the layout of character-decomposition.txt.
+    fn get_decomposition(&self, character: &str) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+pub trait PinyinProvider {
+    /// The accented pinyin of a numbered syllable such as "ren2".
+    fn get_pinyin(&self, wade_giles: &str) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+fn get_page(source: &'static str, base_url: &str, key: &str) -> Page {
+    Page {
+        source,
+        key: key.to_owned(),
+        extension: "html",
+        url: format!("{}{}", base_url, encode(key)),
+        form: None,
+    }
+}
+
+pub struct Hanzicraft<F: Fetcher> {
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> DecompositionProvider for Hanzicraft<F> {
+    fn get_decomposition(&self, character: &str) -> Result<Option<String>, Box<dyn Error>> {
+        const BASE_URL: &str = "https://hanzicraft.com/character/";
+        let page = get_page("hanzicraft", BASE_URL, character);
+
+        Ok(self
+            .fetcher
+            .fetch(&page)?
+            .map(|pr| parse_decomposition_from_hanzicraft(character, &pr)))
+    }
+}
+
+pub struct Wiktionary<F: Fetcher> {
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> StrokeCountProvider for Wiktionary<F> {
+    fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+        const BASE_URL: &str = "https://en.wiktionary.org/wiki/";
+        let page = get_page("wiktionary", BASE_URL, character);
+
+        match self.fetcher.fetch(&page)? {
+            Some(html) => parse_stroke_count_from_wiktionary(&html),
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct Nihongo<F: Fetcher> {
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> StrokeCountProvider for Nihongo<F> {
+    fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+        const BASE_URL: &str = "https://www.nihongo-pro.com/kanji-pal/kanji/";
+        let page = get_page("nihongo", BASE_URL, character);
+
+        match self.fetcher.fetch(&page)? {
+            Some(html) => parse_stroke_count_from_nihongo(&html),
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct Strokeorder<F: Fetcher> {
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> StrokeCountProvider for Strokeorder<F> {
+    fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+        const BASE_URL: &str = "http://www.strokeorder.info/mandarin.php?q=";
+        let page = get_page("strokeorder", BASE_URL, character);
+
+        match self.fetcher.fetch(&page)? {
+            Some(html) => parse_stroke_count_from_strokeorder(&html),
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct Writtenchinese<F: Fetcher> {
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> PinyinProvider for Writtenchinese<F> {
+    fn get_pinyin(&self, wade_giles: &str) -> Result<Option<String>, Box<dyn Error>> {
+        const URL: &str = "https://dictionary.writtenchinese.com/ajaxsearch/simsearch.action";
+
+        let mut form = HashMap::new();
+        form.insert("searchKey", wade_giles.to_owned());
+
+        let page = Page {
+            source: "writtenchinese",
+            key: wade_giles.to_owned(),
+            extension: "json",
+            url: URL.to_owned(),
+            form: Some(form),
+        };
+
+        match self.fetcher.fetch(&page)? {
+            Some(body) => parse_pinyin_from_writtenchinese(&body),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Offline source backed by the kTotalStrokes field of the Unihan database.
+pub struct Unihan {
+    pub records: HashMap<String, UnihanRecord>,
+}
+
+impl StrokeCountProvider for Unihan {
+    fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+        Ok(self.records.get(character).and_then(|pr| pr.total_strokes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::fetcher::FixtureFetcher;
+    use std::env;
+
+    fn get_fetcher() -> FixtureFetcher {
+        FixtureFetcher::new(env::current_dir().unwrap().join("assets/fixtures"))
+    }
+
+    #[test]
+    fn should_read_providers_from_fixtures() {
+        let stroke_count_providers: Vec<Box<dyn StrokeCountProvider>> = vec![
+            Box::new(Wiktionary {
+                fetcher: get_fetcher(),
+            }),
+            Box::new(Nihongo {
+                fetcher: get_fetcher(),
+            }),
+            Box::new(Strokeorder {
+                fetcher: get_fetcher(),
+            }),
+        ];
+
+        for provider in &stroke_count_providers {
+            assert_eq!(provider.get_stroke_count("要").unwrap(), Some(9));
+            assert_eq!(provider.get_stroke_count("x").unwrap(), None);
+        }
+
+        let hanzicraft = Hanzicraft {
+            fetcher: get_fetcher(),
+        };
+        assert_eq!(
+            hanzicraft.get_decomposition("要").unwrap().unwrap(),
+            "要;覀, 女;覀 (west), 女 (woman);覀, ㇛, 一, 丿"
+        );
+
+        let writtenchinese = Writtenchinese {
+            fetcher: get_fetcher(),
+        };
+        assert_eq!(
+            writtenchinese.get_pinyin("ren2").unwrap(),
+            Some("rén".to_string())
+        );
+        assert_eq!(writtenchinese.get_pinyin("x1").unwrap(), None);
+    }
+}
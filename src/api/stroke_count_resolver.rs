@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::api::providers::StrokeCountProvider;
+use crate::models::ComponentTable;
+
+pub static COMPONENTS_SOURCE: &str = "components";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedStrokeCount {
+    pub stroke_count: u8,
+    pub source: String,
+}
+
+/// Sources that gave different stroke counts for the same character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeCountConflict {
+    pub character: String,
+    pub counts: Vec<(String, u8)>,
+}
+
+impl StrokeCountConflict {
+    /// "要 wiktionary=9 nihongo=10"
+    pub fn to_line(&self) -> String {
+        let counts: Vec<String> = self
+            .counts
+            .iter()
+            .map(|(source, stroke_count)| format!("{}={}", source, stroke_count))
+            .collect();
+
+        format!("{} {}", self.character, counts.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution {
+    pub resolved: Option<ResolvedStrokeCount>,
+    pub conflict: Option<StrokeCountConflict>,
+}
+
+pub struct StrokeCountResolver {
+    providers: Vec<(String, Box<dyn StrokeCountProvider>)>,
+    table: ComponentTable,
+    stroke_counts: HashMap<String, u8>,
+    trusted_sources: HashSet<String>,
+    cross_check: bool,
+}
+
+impl StrokeCountResolver {
+    /// `providers` are tried in order. `table` and `stroke_counts` back the
+    /// last resort of adding up the strokes of a character's components.
+    pub fn new(
+        providers: Vec<(String, Box<dyn StrokeCountProvider>)>,
+        table: ComponentTable,
+        stroke_counts: HashMap<String, u8>,
+    ) -> Self {
+        Self {
+            providers,
+            table,
+            stroke_counts,
+            trusted_sources: HashSet::new(),
+            cross_check: false,
+        }
+    }
+
+    /// Once one of `sources` has the character the remaining ones are not
+    /// asked, which saves the web requests behind them, unless `cross_check`
+    /// is set.
+    pub fn with_trusted_sources(mut self, sources: &[&str], cross_check: bool) -> Self {
+        self.trusted_sources = sources.iter().map(|pr| pr.to_string()).collect();
+        self.cross_check = cross_check;
+        self
+    }
+
+    /// Asks every source so that disagreements surface, keeping the answer of
+    /// the first one that has the character, but stops at a trusted one unless
+    /// cross-checking. Fails only when no source had an answer and at least one
+    /// of them errored, so the character can be retried.
+    pub fn resolve(&mut self, character: &str) -> Result<Resolution, Box<dyn Error>> {
+        let mut counts: Vec<(String, u8)> = Vec::new();
+        let mut last_error: Option<Box<dyn Error>> = None;
+
+        for (source, provider) in &self.providers {
+            match provider.get_stroke_count(character) {
+                Ok(Some(stroke_count)) => {
+                    counts.push((source.to_owned(), stroke_count));
+
+                    if !self.cross_check && self.trusted_sources.contains(source) {
+                        break;
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    warn!("{} failed for {}: {}", source, character, error);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        let conflict = if counts.iter().any(|pr| pr.1 != counts[0].1) {
+            Some(StrokeCountConflict {
+                character: character.to_owned(),
+                counts: counts.clone(),
+            })
+        } else {
+            None
+        };
+
+        let resolved = match counts.into_iter().next() {
+            Some((source, stroke_count)) => Some(ResolvedStrokeCount {
+                stroke_count,
+                source,
+            }),
+            None => {
+                if let Some(error) = last_error {
+                    return Err(error);
+                }
+
+                self.get_component_stroke_count(character)
+                    .map(|stroke_count| ResolvedStrokeCount {
+                        stroke_count,
+                        source: COMPONENTS_SOURCE.to_string(),
+                    })
+            }
+        };
+
+        if let Some(resolved) = &resolved {
+            self.stroke_counts
+                .insert(character.to_owned(), resolved.stroke_count);
+        }
+
+        Ok(Resolution { resolved, conflict })
+    }
+
+    /// The sum of the stroke counts of the immediate components, looking
+    /// further down for components whose count isn't known. `None` when any
+    /// component is missing a glyph or a count.
+    pub fn get_component_stroke_count(&self, character: &str) -> Option<u8> {
+        let mut ancestors: Vec<&str> = Vec::new();
+        self.sum_components(character, &mut ancestors)
+    }
+
+    fn sum_components<'a>(
+        &'a self,
+        character: &'a str,
+        ancestors: &mut Vec<&'a str>,
+    ) -> Option<u8> {
+        if ancestors.contains(&character) {
+            return None;
+        }
+
+        let components = self.table.components.get(character)?;
+        let components: Vec<&String> = components
+            .iter()
+            .map(|pr| pr.as_ref())
+            .collect::<Option<Vec<&String>>>()?
+            .into_iter()
+            .filter(|pr| *pr != character)
+            .collect();
+
+        if components.is_empty() {
+            return None;
+        }
+
+        ancestors.push(character);
+        let mut sum: u8 = 0;
+
+        for component in components {
+            let stroke_count = match self.stroke_counts.get(component.as_str()) {
+                Some(stroke_count) => Some(*stroke_count),
+                None => self.sum_components(component, ancestors),
+            };
+
+            match stroke_count.and_then(|pr| sum.checked_add(pr)) {
+                Some(value) => sum = value,
+                None => {
+                    ancestors.pop();
+                    return None;
+                }
+            }
+        }
+
+        ancestors.pop();
+        Some(sum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct FakeProvider {
+        stroke_counts: HashMap<&'static str, u8>,
+        fails: bool,
+    }
+
+    impl StrokeCountProvider for FakeProvider {
+        fn get_stroke_count(&self, character: &str) -> Result<Option<u8>, Box<dyn Error>> {
+            if self.fails {
+                return Err("timeout".into());
+            }
+
+            Ok(self.stroke_counts.get(character).copied())
+        }
+    }
+
+    fn get_provider(items: &[(&'static str, u8)], fails: bool) -> Box<dyn StrokeCountProvider> {
+        Box::new(FakeProvider {
+            stroke_counts: items.iter().cloned().collect(),
+            fails,
+        })
+    }
+
+    fn get_resolver(fails: bool) -> StrokeCountResolver {
+        let mut table = ComponentTable::default();
+
+        for (character, components) in &[
+            ("好", vec![Some("女"), Some("子")]),
+            ("娶", vec![Some("取"), Some("女")]),
+            ("取", vec![Some("耳"), Some("又")]),
+            ("塍", vec![Some("朕"), None]),
+        ] {
+            table.components.insert(
+                character.to_string(),
+                components
+                    .iter()
+                    .map(|pr| pr.map(|pr| pr.to_string()))
+                    .collect(),
+            );
+        }
+
+        let stroke_counts: HashMap<String, u8> = vec![("女", 3), ("子", 3), ("耳", 6), ("又", 2)]
+            .into_iter()
+            .map(|(character, stroke_count)| (character.to_string(), stroke_count))
+            .collect();
+
+        StrokeCountResolver::new(
+            vec![
+                ("wiktionary".to_string(), get_provider(&[("要", 9)], false)),
+                (
+                    "nihongo".to_string(),
+                    get_provider(&[("要", 9), ("西", 6)], fails),
+                ),
+                (
+                    "strokeorder".to_string(),
+                    get_provider(&[("要", 10), ("西", 6)], false),
+                ),
+            ],
+            table,
+            stroke_counts,
+        )
+    }
+
+    #[test]
+    fn should_resolve_in_order_and_report_conflicts() {
+        let mut resolver = get_resolver(false);
+
+        let resolution = resolver.resolve("要").unwrap();
+        assert_eq!(
+            resolution.resolved,
+            Some(ResolvedStrokeCount {
+                stroke_count: 9,
+                source: "wiktionary".to_string()
+            })
+        );
+        assert_eq!(
+            resolution.conflict.unwrap().to_line(),
+            "要 wiktionary=9 nihongo=9 strokeorder=10"
+        );
+
+        let resolution = resolver.resolve("西").unwrap();
+        assert_eq!(resolution.resolved.unwrap().source, "nihongo");
+        assert_eq!(resolution.conflict, None);
+    }
+
+    #[test]
+    fn should_fall_back_to_component_strokes() {
+        let mut resolver = get_resolver(false);
+
+        let resolution = resolver.resolve("娶").unwrap();
+        assert_eq!(
+            resolution.resolved,
+            Some(ResolvedStrokeCount {
+                stroke_count: 11,
+                source: COMPONENTS_SOURCE.to_string()
+            })
+        );
+        assert_eq!(resolver.get_component_stroke_count("好"), Some(6));
+        assert_eq!(resolver.get_component_stroke_count("塍"), None);
+        assert_eq!(resolver.resolve("x").unwrap().resolved, None);
+    }
+
+    #[test]
+    fn should_fail_when_nothing_resolved_and_a_source_errored() {
+        let mut resolver = get_resolver(true);
+
+        assert_eq!(
+            resolver
+                .resolve("要")
+                .unwrap()
+                .resolved
+                .unwrap()
+                .stroke_count,
+            9
+        );
+        assert!(resolver.resolve("好").is_err());
+    }
+
+    struct CountingProvider {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl StrokeCountProvider for CountingProvider {
+        fn get_stroke_count(&self, _: &str) -> Result<Option<u8>, Box<dyn Error>> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(Some(10))
+        }
+    }
+
+    fn get_trusting_resolver(calls: &Rc<Cell<usize>>, cross_check: bool) -> StrokeCountResolver {
+        StrokeCountResolver::new(
+            vec![
+                ("unihan".to_string(), get_provider(&[("要", 9)], false)),
+                (
+                    "wiktionary".to_string(),
+                    Box::new(CountingProvider {
+                        calls: calls.clone(),
+                    }),
+                ),
+            ],
+            ComponentTable::default(),
+            HashMap::new(),
+        )
+        .with_trusted_sources(&["unihan"], cross_check)
+    }
+
+    #[test]
+    fn should_not_ask_web_sources_after_a_trusted_one() {
+        let calls = Rc::new(Cell::new(0));
+        let mut resolver = get_trusting_resolver(&calls, false);
+
+        let resolution = resolver.resolve("要").unwrap();
+        assert_eq!(resolution.resolved.unwrap().source, "unihan");
+        assert_eq!(resolution.conflict, None);
+        assert_eq!(calls.get(), 0);
+
+        let resolution = resolver.resolve("西").unwrap();
+        assert_eq!(resolution.resolved.unwrap().source, "wiktionary");
+        assert_eq!(calls.get(), 1);
+
+        let mut resolver = get_trusting_resolver(&calls, true);
+        let resolution = resolver.resolve("要").unwrap();
+        assert_eq!(
+            resolution.conflict.unwrap().to_line(),
+            "要 unihan=9 wiktionary=10"
+        );
+        assert_eq!(calls.get(), 2);
+    }
+}
@@ -0,0 +1,622 @@
+use crate::exporter::export_stroke_order_svgs::render_animated_svg;
+use crate::models::*;
+use crate::utils::escape_html::escape_html;
+use crate::utils::get_entry_path::get_entry_path;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::path::Path;
+
+static STYLE: &str = r#"body { font-family: sans-serif; margin: 0 auto; max-width: 48em; padding: 1em; }
+a { color: #1a5fb4; text-decoration: none; }
+a:hover { text-decoration: underline; }
+[lang="zh"] { font-size: 1.2em; }
+h1 [lang="zh"] { font-size: 2em; }
+.detail { border-top: 1px solid #ddd; padding: 0.5em 0; }
+.pronunciation { color: #555; }
+.context, .lexical-item, .tag { background: #eee; border-radius: 3px; font-size: 0.8em; margin-right: 0.3em; padding: 0 0.3em; }
+.literal { color: #555; font-style: italic; }
+.examples li { padding: 0.2em 0; }
+.examples [lang="zh"] { margin-right: 0.5em; }
+#results li { padding: 0.2em 0; }
+"#;
+
+static SEARCH_SCRIPT: &str = r#"(function () {
+    var input = document.getElementById("query");
+    var results = document.getElementById("results");
+    var index = [];
+
+    function normalize(value) {
+        return value
+            .normalize("NFD")
+            .replace(/[\u0300-\u036f]/g, "")
+            .replace(/[\s\d]/g, "")
+            .toLowerCase();
+    }
+
+    function search() {
+        var query = input.value.trim();
+        var normalized = normalize(query);
+        var lowercase = query.toLowerCase();
+        results.innerHTML = "";
+
+        if (!query) {
+            return;
+        }
+
+        var matches = index.filter(function (entry) {
+            return entry.simplified.indexOf(query) !== -1
+                || entry.traditional.some(function (value) { return value.indexOf(query) !== -1; })
+                || (normalized && entry.pinyin.some(function (value) { return normalize(value).indexOf(normalized) === 0; }))
+                || entry.gloss.toLowerCase().indexOf(lowercase) !== -1;
+        }).slice(0, 100);
+
+        matches.forEach(function (entry) {
+            var item = document.createElement("li");
+            var link = document.createElement("a");
+            link.href = entry.url;
+            link.lang = "zh";
+            link.textContent = entry.simplified;
+            item.appendChild(link);
+            item.appendChild(document.createTextNode(" " + entry.pinyin.join(", ") + " " + entry.gloss));
+            results.appendChild(item);
+        });
+    }
+
+    fetch("search-index.json")
+        .then(function (response) { return response.json(); })
+        .then(function (data) {
+            index = data;
+            input.addEventListener("input", search);
+            search();
+        });
+})();
+"#;
+
+#[derive(Serialize)]
+struct SearchIndexEntry {
+    ids: Vec<String>,
+    simplified: String,
+    traditional: Vec<String>,
+    pinyin: Vec<String>,
+    gloss: String,
+    url: String,
+}
+
+pub fn export_html_site(
+    groups: &[Group],
+    hsk_levels: &[HskLevel],
+    public_directory: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let entries_directory = public_directory.join("entries");
+    let hsk_directory = public_directory.join("hsk");
+    fs::create_dir_all(&entries_directory)?;
+    fs::create_dir_all(&hsk_directory)?;
+
+    let mut headwords: HashMap<&str, String> = HashMap::with_capacity(groups.len() * 2);
+
+    for group in groups {
+        headwords.insert(&group.simplified, get_entry_path(&group.simplified, "html"));
+    }
+
+    for group in groups {
+        for detail in &group.details {
+            headwords
+                .entry(&detail.traditional)
+                .or_insert_with(|| get_entry_path(&group.simplified, "html"));
+        }
+    }
+
+    let mut search_index: Vec<SearchIndexEntry> = Vec::with_capacity(groups.len());
+
+    for group in groups {
+        let html = render_entry_page(group, &headwords, hsk_levels);
+        let file_path = entries_directory.join(&headwords[group.simplified.as_str()]);
+        fs::create_dir_all(file_path.parent().unwrap())?;
+        let mut file = File::create(file_path)?;
+        file.write_all(html.as_bytes())?;
+
+        search_index.push(get_search_index_entry(group));
+    }
+
+    for hsk_level in hsk_levels {
+        let html = render_hsk_page(hsk_level, groups);
+        let mut file = File::create(hsk_directory.join(format!("{}.html", hsk_level.tag)))?;
+        file.write_all(html.as_bytes())?;
+    }
+
+    let mut file = File::create(public_directory.join("index.html"))?;
+    file.write_all(render_index_page(hsk_levels).as_bytes())?;
+
+    let mut file = File::create(public_directory.join("style.css"))?;
+    file.write_all(STYLE.as_bytes())?;
+
+    let mut file = File::create(public_directory.join("search.js"))?;
+    file.write_all(SEARCH_SCRIPT.as_bytes())?;
+
+    let file = File::create(public_directory.join("search-index.json"))?;
+    let buffer_writer = BufWriter::new(file);
+    serde_json::to_writer(buffer_writer, &search_index)?;
+
+    Ok(())
+}
+
+fn get_search_index_entry(group: &Group) -> SearchIndexEntry {
+    let mut ids: Vec<String> = Vec::new();
+    let mut traditional: Vec<String> = Vec::new();
+    let mut pinyin: Vec<String> = Vec::new();
+    let mut gloss: Option<String> = None;
+
+    for detail in &group.details {
+        ids.push(detail.id.to_owned());
+
+        if detail.traditional != group.simplified && !traditional.contains(&detail.traditional) {
+            traditional.push(detail.traditional.to_owned());
+        }
+
+        for pronunciation in &detail.pronunciation {
+            if !pronunciation.pinyin.is_empty() && !pinyin.contains(&pronunciation.pinyin) {
+                pinyin.push(pronunciation.pinyin.to_owned());
+            }
+        }
+
+        if gloss.is_none() {
+            gloss = detail
+                .meanings
+                .iter()
+                .find_map(|pr| pr.value.clone().or_else(|| pr.literal_meaning.clone()));
+        }
+    }
+
+    SearchIndexEntry {
+        ids,
+        simplified: group.simplified.to_owned(),
+        traditional,
+        pinyin,
+        gloss: gloss.unwrap_or_default(),
+        url: format!("entries/{}", get_entry_path(&group.simplified, "html")),
+    }
+}
+
+fn get_hsk_label(hsk_level: &HskLevel) -> String {
+    format!("HSK {}.0 level {}", hsk_level.version, hsk_level.level)
+}
+
+fn render_page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n<body>\n<nav><a href=\"{}index.html\">Search</a></nav>\n{}</body>\n</html>\n",
+        escape_html(title),
+        root,
+        root,
+        body
+    )
+}
+
+fn render_headword(
+    headword: &str,
+    reference_id: Option<&String>,
+    headwords: &HashMap<&str, String>,
+) -> String {
+    match headwords.get(headword) {
+        Some(path) => format!(
+            "<a href=\"../{}{}\" lang=\"zh\">{}</a>",
+            path,
+            reference_id
+                .map(|pr| format!("#{}", pr))
+                .unwrap_or_default(),
+            escape_html(headword)
+        ),
+        None => format!("<span lang=\"zh\">{}</span>", escape_html(headword)),
+    }
+}
+
+fn render_reference(
+    simplified: &str,
+    traditional: Option<&String>,
+    wade_giles_pinyin: Option<&String>,
+    reference_id: Option<&String>,
+    headwords: &HashMap<&str, String>,
+) -> String {
+    let mut result = render_headword(simplified, reference_id, headwords);
+
+    if let Some(traditional) = traditional.filter(|pr| !pr.is_empty() && *pr != simplified) {
+        result = result + "|" + &render_headword(traditional, reference_id, headwords);
+    }
+
+    if let Some(wade_giles_pinyin) = wade_giles_pinyin.filter(|pr| !pr.is_empty()) {
+        result = result + &format!(" [{}]", escape_html(wade_giles_pinyin));
+    }
+
+    result
+}
+
+fn render_meaning(meaning: &Meaning, headwords: &HashMap<&str, String>) -> String {
+    let mut result = String::new();
+
+    for context in meaning.context.as_ref().unwrap_or(&Vec::new()) {
+        result = result + &format!("<span class=\"context\">{}</span>", escape_html(context));
+    }
+
+    if let Some(lexical_item) = &meaning.lexical_item {
+        result = result
+            + &format!(
+                "<span class=\"lexical-item\">{}</span>",
+                escape_html(lexical_item)
+            );
+    }
+
+    if let Some(simplified) = &meaning.simplified {
+        result = result
+            + &render_reference(
+                simplified,
+                meaning.traditional.as_ref(),
+                meaning.wade_giles_pinyin.as_ref(),
+                meaning.reference_id.as_ref(),
+                headwords,
+            )
+            + " ";
+    }
+
+    if let Some(value) = &meaning.value {
+        result = result + &escape_html(value);
+    }
+
+    if let Some(literal_meaning) = &meaning.literal_meaning {
+        result = result
+            + &format!(
+                " <span class=\"literal\">lit. {}</span>",
+                escape_html(literal_meaning)
+            );
+    }
+
+    result
+}
+
+fn render_detail(detail: &Detail, headwords: &HashMap<&str, String>) -> String {
+    let mut result = format!(
+        "<section class=\"detail\" id=\"{}\">\n",
+        escape_html(&detail.id)
+    );
+
+    result = result
+        + &format!(
+            "<h2><span lang=\"zh\">{}</span>",
+            escape_html(&detail.simplified)
+        );
+
+    if detail.traditional != detail.simplified {
+        result = result
+            + &format!(
+                " <span lang=\"zh-Hant\">{}</span>",
+                escape_html(&detail.traditional)
+            );
+    }
+
+    result = result + "</h2>\n";
+
+    let pronunciations: Vec<String> = detail
+        .pronunciation
+        .iter()
+        .map(|pr| match &pr.other {
+            Some(other) => escape_html(other),
+            None => format!(
+                "{} ({})",
+                escape_html(&pr.pinyin),
+                escape_html(&pr.wade_giles_pinyin)
+            ),
+        })
+        .chain(
+            detail
+                .pronunciation
+                .iter()
+                .filter_map(|pr| pr.jyutping.as_ref())
+                .map(|pr| format!("Jyutping: {}", escape_html(&pr.join(" / ")))),
+        )
+        .collect();
+
+    result = result
+        + &format!(
+            "<p class=\"pronunciation\">{}</p>\n",
+            pronunciations.join(", ")
+        );
+
+    if let Some(stroke_count) = detail.traditional_stroke_count {
+        result = result + &format!("<p>Strokes: {}</p>\n", stroke_count);
+    }
+
+    result = result + "<ol class=\"meanings\">\n";
+
+    for meaning in &detail.meanings {
+        result = result + &format!("<li>{}</li>\n", render_meaning(meaning, headwords));
+    }
+
+    result = result + "</ol>\n";
+
+    if let Some(classifiers) = &detail.classifiers {
+        let classifiers: Vec<String> = classifiers
+            .iter()
+            .map(|pr| {
+                render_reference(
+                    &pr.simplified,
+                    Some(&pr.traditional),
+                    Some(&pr.wade_giles_pinyin),
+                    pr.reference_id.as_ref(),
+                    headwords,
+                )
+            })
+            .collect();
+
+        result = result + &format!("<p>Classifiers: {}</p>\n", classifiers.join(", "));
+    }
+
+    if let Some(variant) = &detail.variant {
+        result = result
+            + &format!(
+                "<p>Also written: {}</p>\n",
+                render_reference(
+                    &variant.simplified,
+                    variant.traditional.as_ref(),
+                    variant.wade_giles_pinyin.as_ref(),
+                    variant.reference_id.as_ref(),
+                    headwords,
+                )
+            );
+    }
+
+    if let Some(decomposition) = &detail.decomposition {
+        let radicals: Vec<String> = decomposition
+            .radical
+            .iter()
+            .map(|pr| {
+                format!(
+                    "{} ({})",
+                    render_headword(&pr.value, None, headwords),
+                    escape_html(&pr.meaning)
+                )
+            })
+            .collect();
+
+        result = result + &format!("<p>Radicals: {}</p>\n", radicals.join(", "));
+
+        if let Some(ids) = &decomposition.ids {
+            result = result + &format!("<p>Structure: {}</p>\n", escape_html(ids));
+        }
+    }
+
+    if let Some(similar) = &detail.similar {
+        let similar: Vec<String> = similar
+            .iter()
+            .map(|pr| render_headword(pr, None, headwords))
+            .collect();
+
+        result = result + &format!("<p>Similar: {}</p>\n", similar.join(", "));
+    }
+
+    if let Some(strokes) = &detail.strokes {
+        result = result
+            + &format!(
+                "<figure class=\"strokes\">\n{}</figure>\n",
+                render_animated_svg(strokes, &detail.id)
+            );
+    }
+
+    if let Some(examples) = &detail.examples {
+        result = result + "<ul class=\"examples\">\n";
+
+        for example in examples {
+            let translations: Vec<String> = example
+                .translations
+                .iter()
+                .map(|pr| {
+                    format!(
+                        "<span lang=\"{}\">{}</span>",
+                        escape_html(&pr.language),
+                        escape_html(&pr.value)
+                    )
+                })
+                .collect();
+
+            result = result
+                + &format!(
+                    "<li><span lang=\"zh\">{}</span> {}</li>\n",
+                    escape_html(&example.value),
+                    translations.join(" / ")
+                );
+        }
+
+        result = result + "</ul>\n";
+    }
+
+    if let Some(tags) = &detail.tags {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|pr| format!("<span class=\"tag\">{}</span>", escape_html(pr)))
+            .collect();
+
+        result = result + &format!("<p class=\"tags\">{}</p>\n", tags.join(""));
+    }
+
+    result + "</section>\n"
+}
+
+fn render_entry_page(
+    group: &Group,
+    headwords: &HashMap<&str, String>,
+    hsk_levels: &[HskLevel],
+) -> String {
+    let mut body = format!(
+        "<h1><span lang=\"zh\">{}</span></h1>\n",
+        escape_html(&group.simplified)
+    );
+
+    let levels: Vec<String> = hsk_levels
+        .iter()
+        .filter(|pr| pr.words.contains(&group.simplified))
+        .map(|pr| {
+            format!(
+                "<a href=\"../../hsk/{}.html\">{}</a>",
+                pr.tag,
+                get_hsk_label(pr)
+            )
+        })
+        .collect();
+
+    if !levels.is_empty() {
+        body = body + &format!("<p class=\"hsk\">{}</p>\n", levels.join(", "));
+    }
+
+    for detail in &group.details {
+        body = body + &render_detail(detail, headwords);
+    }
+
+    render_page(&group.simplified, "../../", &body)
+}
+
+fn render_hsk_page(hsk_level: &HskLevel, groups: &[Group]) -> String {
+    let label = get_hsk_label(hsk_level);
+    let mut body = format!("<h1>{}</h1>\n<ul>\n", label);
+
+    for group in groups {
+        if !hsk_level.words.contains(&group.simplified) {
+            continue;
+        }
+
+        let pinyin = group
+            .details
+            .first()
+            .and_then(|pr| pr.pronunciation.first())
+            .map(|pr| pr.pinyin.to_owned())
+            .unwrap_or_default();
+
+        body = body
+            + &format!(
+                "<li><a href=\"../entries/{}\" lang=\"zh\">{}</a> {}</li>\n",
+                get_entry_path(&group.simplified, "html"),
+                escape_html(&group.simplified),
+                escape_html(&pinyin)
+            );
+    }
+
+    body = body + "</ul>\n";
+
+    render_page(&label, "../", &body)
+}
+
+fn render_index_page(hsk_levels: &[HskLevel]) -> String {
+    let mut body = String::from("<h1>CC-CEDICT</h1>\n<input id=\"query\" type=\"search\" placeholder=\"漢字, hanzi, pinyin or English\" autofocus>\n<ul id=\"results\"></ul>\n<h2>HSK</h2>\n<ul>\n");
+
+    for hsk_level in hsk_levels {
+        body = body
+            + &format!(
+                "<li><a href=\"hsk/{}.html\">{}</a></li>\n",
+                hsk_level.tag,
+                get_hsk_label(hsk_level)
+            );
+    }
+
+    body = body + "</ul>\n<script src=\"search.js\"></script>\n";
+
+    render_page("CC-CEDICT", "", &body)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::get_detail_id::get_detail_id;
+    use std::collections::HashSet;
+    use std::env;
+
+    fn get_group(simplified: &str, traditional: &str, meanings: Vec<Meaning>) -> Group {
+        Group {
+            simplified: simplified.to_string(),
+            details: vec![Detail {
+                id: get_detail_id(traditional, simplified, "di4 jiao4"),
+                pronunciation: vec![Pronunciation {
+                    pinyin: "dì jiào".to_string(),
+                    wade_giles_pinyin: "di4 jiao4".to_string(),
+                    ..Default::default()
+                }],
+                simplified: simplified.to_string(),
+                traditional: traditional.to_string(),
+                meanings,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn get_meaning(value: &str) -> Meaning {
+        Meaning {
+            value: Some(value.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_link_references() {
+        let mut reference = get_meaning("basement");
+        reference.context = Some(vec!["see".to_string()]);
+        reference.simplified = Some("地下室".to_string());
+        reference.wade_giles_pinyin = Some("di4 xia4 shi4".to_string());
+        reference.reference_id = Some("0123456789abcdef".to_string());
+        let mut groups = vec![
+            get_group("地窖", "地窖", vec![get_meaning("cellar"), reference]),
+            get_group("地下室", "地下室", vec![get_meaning("basement")]),
+        ];
+        groups[0].details[0].pronunciation[0].jyutping = Some(vec!["dei6 gaau3".to_string()]);
+        groups[0].details[0].examples = Some(vec![Example {
+            id: 1,
+            value: "地窖里很冷。".to_string(),
+            translations: vec![Translation {
+                language: "en".to_string(),
+                value: "It is cold in the cellar.".to_string(),
+            }],
+        }]);
+        let mut headwords: HashMap<&str, String> = HashMap::new();
+        headwords.insert("地窖", get_entry_path("地窖", "html"));
+        headwords.insert("地下室", get_entry_path("地下室", "html"));
+        let hsk_levels = vec![HskLevel {
+            tag: "hsk-2-6".to_string(),
+            version: 2,
+            level: 6,
+            words: vec!["地窖".to_string()].into_iter().collect::<HashSet<_>>(),
+        }];
+
+        let html = render_entry_page(&groups[0], &headwords, &hsk_levels);
+
+        assert!(html.contains("<li>cellar</li>"));
+        assert!(html.contains("dì jiào (di4 jiao4), Jyutping: dei6 gaau3</p>"));
+        assert!(html.contains(
+            "<li><span lang=\"zh\">地窖里很冷。</span> <span lang=\"en\">It is cold in the cellar.</span></li>"
+        ));
+        assert!(html.contains(&format!(
+            "<a href=\"../{}#0123456789abcdef\" lang=\"zh\">地下室</a> [di4 xia4 shi4]",
+            get_entry_path("地下室", "html")
+        )));
+        assert!(html.contains("<a href=\"../../hsk/hsk-2-6.html\">HSK 2.0 level 6</a>"));
+    }
+
+    #[test]
+    fn should_export_html_site() {
+        let public_directory = env::temp_dir().join("refined-cedict-json-html-site");
+        let groups = vec![get_group("地窖", "地窖", vec![get_meaning("cellar")])];
+
+        export_html_site(&groups, &[], &public_directory).unwrap();
+
+        assert!(public_directory
+            .join("entries")
+            .join(get_entry_path("地窖", "html"))
+            .exists());
+        assert!(public_directory.join("index.html").exists());
+        let bytes = fs::read(public_directory.join("search-index.json")).unwrap();
+        let search_index: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(search_index[0]["gloss"], "cellar");
+        assert_eq!(search_index[0]["ids"][0], groups[0].details[0].id.as_str());
+        assert_eq!(search_index[0]["pinyin"][0], "dì jiào");
+        fs::remove_dir_all(&public_directory).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use crate::models::*;
+use crate::utils::get_entry_path::get_entry_path;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    headwords: BTreeMap<&'a str, String>,
+    ids: BTreeMap<&'a str, String>,
+}
+
+pub fn export_json_files(groups: &[Group], output_directory: &Path) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest {
+        headwords: BTreeMap::new(),
+        ids: BTreeMap::new(),
+    };
+
+    for group in groups {
+        let path = get_entry_path(&group.simplified, "json");
+        let file_path = output_directory.join(&path);
+        fs::create_dir_all(file_path.parent().unwrap())?;
+
+        let file = File::create(file_path)?;
+        let buffer_writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(buffer_writer, group)?;
+
+        for detail in &group.details {
+            manifest.ids.insert(&detail.id, path.to_owned());
+        }
+
+        manifest.headwords.insert(&group.simplified, path);
+    }
+
+    let file = File::create(output_directory.join("manifest.json"))?;
+    let buffer_writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(buffer_writer, &manifest)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::env;
+
+    #[test]
+    fn should_export_json_files_with_manifest() {
+        let output_directory = env::temp_dir().join("refined-cedict-json-json-files");
+        let mut groups: Vec<Group> = vec!["A菜", "T恤", "地窖"]
+            .into_iter()
+            .map(|pr| Group {
+                simplified: pr.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        groups[2].details.push(Detail {
+            id: "1".to_string(),
+            simplified: "地窖".to_string(),
+            traditional: "地窖".to_string(),
+            ..Default::default()
+        });
+
+        export_json_files(&groups, &output_directory).unwrap();
+
+        let bytes = fs::read(output_directory.join("manifest.json")).unwrap();
+        let manifest: HashMap<String, HashMap<String, String>> =
+            serde_json::from_slice(&bytes).unwrap();
+        let headwords = &manifest["headwords"];
+        assert_eq!(headwords.len(), 3);
+        assert!(headwords["A菜"].ends_with("/_41_菜.json"));
+        assert_eq!(manifest["ids"]["1"], headwords["地窖"]);
+
+        for path in headwords.values() {
+            assert!(output_directory.join(path).exists());
+        }
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+}
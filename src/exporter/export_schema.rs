@@ -0,0 +1,379 @@
+use crate::models::Group;
+use schemars::schema_for;
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+pub static JSON_SCHEMA_FILE_NAME: &str = "refined.schema.json";
+pub static TYPESCRIPT_DEFINITIONS_FILE_NAME: &str = "refined.d.ts";
+
+pub fn get_json_schema() -> Result<Value, Box<dyn Error>> {
+    let schema = schema_for!(Group);
+    let value = serde_json::to_value(&schema)?;
+    Ok(value)
+}
+
+pub fn get_typescript_definitions(schema: &Value) -> String {
+    let mut result = String::from(
+        "// Generated from the JSON schema of the refined format. Do not edit by hand.\n",
+    );
+
+    let mut definitions: Vec<(&str, &Value)> = Vec::new();
+
+    if let Some(title) = schema["title"].as_str() {
+        definitions.push((title, schema));
+    }
+
+    if let Some(items) = schema["definitions"].as_object() {
+        for (name, definition) in items {
+            definitions.push((name, definition));
+        }
+    }
+
+    for (name, definition) in definitions {
+        result = result + &format!("\nexport interface {} {{\n", name);
+
+        let required: Vec<&str> = definition["required"]
+            .as_array()
+            .map(|pr| pr.iter().filter_map(|pr| pr.as_str()).collect())
+            .unwrap_or_default();
+
+        if let Some(properties) = definition["properties"].as_object() {
+            for (property, value) in properties {
+                if required.contains(&property.as_str()) {
+                    result =
+                        result + &format!("    {}: {};\n", property, get_typescript_type(value));
+                } else {
+                    result = result
+                        + &format!(
+                            "    {}?: {};\n",
+                            property,
+                            get_typescript_type(&without_null(value))
+                        );
+                }
+            }
+        }
+
+        result = result + "}\n";
+    }
+
+    result
+}
+
+fn without_null(value: &Value) -> Value {
+    let mut value = value.clone();
+
+    if let Some(types) = value["type"].as_array() {
+        let types: Vec<Value> = types
+            .iter()
+            .filter(|pr| pr.as_str() != Some("null"))
+            .cloned()
+            .collect();
+
+        value["type"] = if types.len() == 1 {
+            types[0].clone()
+        } else {
+            Value::Array(types)
+        };
+    }
+
+    if let Some(variants) = value["anyOf"].as_array() {
+        let variants: Vec<Value> = variants
+            .iter()
+            .filter(|pr| pr["type"].as_str() != Some("null"))
+            .cloned()
+            .collect();
+
+        if variants.len() == 1 {
+            return variants[0].clone();
+        }
+
+        value["anyOf"] = Value::Array(variants);
+    }
+
+    value
+}
+
+fn get_typescript_type(value: &Value) -> String {
+    if let Some(reference) = value["$ref"].as_str() {
+        return reference.rsplit('/').next().unwrap_or_default().to_string();
+    }
+
+    if let Some(variants) = value["anyOf"].as_array() {
+        let types: Vec<String> = variants.iter().map(get_typescript_type).collect();
+        return types.join(" | ");
+    }
+
+    if let Some(types) = value["type"].as_array() {
+        let types: Vec<String> = types
+            .iter()
+            .map(|pr| {
+                let mut single = value.clone();
+                single["type"] = pr.clone();
+                get_typescript_type(&single)
+            })
+            .collect();
+        return types.join(" | ");
+    }
+
+    match value["type"].as_str() {
+        Some("string") => "string".to_string(),
+        Some("integer") | Some("number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => {
+            let item = get_typescript_type(&value["items"]);
+
+            if item.contains(' ') {
+                format!("({})[]", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        Some("object") => match &value["additionalProperties"] {
+            Value::Object(_) => format!(
+                "{{ [key: string]: {} }}",
+                get_typescript_type(&value["additionalProperties"])
+            ),
+            _ => "object".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+pub fn export_schema(output_directory: &Path) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(output_directory)?;
+
+    let schema = get_json_schema()?;
+
+    let mut file = File::create(output_directory.join(JSON_SCHEMA_FILE_NAME))?;
+    file.write_all(serde_json::to_string_pretty(&schema)?.as_bytes())?;
+    file.write_all(b"\n")?;
+
+    let mut file = File::create(output_directory.join(TYPESCRIPT_DEFINITIONS_FILE_NAME))?;
+    file.write_all(get_typescript_definitions(&schema).as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::*;
+    use std::env;
+
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Vec<String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.rsplit('/').next().unwrap();
+            return validate(value, &root["definitions"][name], root, path);
+        }
+
+        if let Some(variants) = schema["anyOf"].as_array() {
+            let valid = variants
+                .iter()
+                .any(|pr| validate(value, pr, root, path).is_empty());
+
+            return if valid {
+                Vec::new()
+            } else {
+                vec![format!("{}: does not match any variant", path)]
+            };
+        }
+
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(value) => vec![value.as_str()],
+            Value::Array(values) => values.iter().filter_map(|pr| pr.as_str()).collect(),
+            _ => Vec::new(),
+        };
+
+        let actual = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(number) if number.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+
+        if !types.is_empty() && !types.contains(&actual) {
+            return vec![format!("{}: expected {:?}, got {}", path, types, actual)];
+        }
+
+        let mut errors = Vec::new();
+
+        match value {
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_path = format!("{}[{}]", path, index);
+                    errors.extend(validate(item, &schema["items"], root, &item_path));
+                }
+            }
+            Value::Object(object) => {
+                for required in schema["required"].as_array().unwrap_or(&Vec::new()) {
+                    if !object.contains_key(required.as_str().unwrap()) {
+                        errors.push(format!("{}: missing {}", path, required));
+                    }
+                }
+
+                for (key, item) in object {
+                    let property = &schema["properties"][key];
+                    let item_path = format!("{}.{}", path, key);
+
+                    if property.is_null() {
+                        errors.push(format!("{}: not in schema", item_path));
+                        continue;
+                    }
+
+                    errors.extend(validate(item, property, root, &item_path));
+                }
+            }
+            _ => {}
+        }
+
+        errors
+    }
+
+    fn get_full_group() -> Group {
+        let meaning = Meaning {
+            reference_id: Some("0123456789abcdef".to_string()),
+            context: Some(vec!["see".to_string()]),
+            lexical_item: Some("idiom".to_string()),
+            value: Some("cellar".to_string()),
+            literal_meaning: Some("earth cellar".to_string()),
+            simplified: Some("地下室".to_string()),
+            traditional: Some("地下室".to_string()),
+            wade_giles_pinyin: Some("di4 xia4 shi4".to_string()),
+            pinyin: Some("dì xià shì".to_string()),
+            language: Some("en".to_string()),
+        };
+        let radical = Radical {
+            value: "土".to_string(),
+            stroke_count: 3,
+            meaning: "earth".to_string(),
+            pinyin: "tǔ".to_string(),
+        };
+
+        Group {
+            simplified: "地窖".to_string(),
+            simplified_stroke_count: Some(18),
+            details: vec![Detail {
+                id: "fedcba9876543210".to_string(),
+                pronunciation: vec![Pronunciation {
+                    pinyin: "dì jiào".to_string(),
+                    wade_giles_pinyin: "di4 jiao4".to_string(),
+                    other: Some("Taiwan pr.".to_string()),
+                    jyutping: Some(vec!["dei6 gaau3".to_string()]),
+                }],
+                simplified: "地窖".to_string(),
+                simplified_stroke_count: Some(18),
+                traditional: "地窖".to_string(),
+                traditional_stroke_count: Some(18),
+                variant: Some(Variant {
+                    reference_id: None,
+                    simplified: "地窨".to_string(),
+                    traditional: Some("地窨".to_string()),
+                    wade_giles_pinyin: Some("di4 yin4".to_string()),
+                }),
+                meanings: vec![meaning],
+                classifiers: Some(vec![Classifier {
+                    reference_id: None,
+                    simplified: "个".to_string(),
+                    traditional: "個".to_string(),
+                    wade_giles_pinyin: "ge4".to_string(),
+                }]),
+                decomposition: Some(Decomposition {
+                    radical: vec![radical],
+                    graphical: vec!["一".to_string()],
+                    ids: Some("⿱土⿱穴告".to_string()),
+                }),
+                similar: Some(vec!["地窨".to_string()]),
+                strokes: Some(vec![Stroke {
+                    path: "M 322 640 Q 318 596 256 544 L 287 526 Q 342 578 352 622 Z".to_string(),
+                    median: vec![[327, 636], [301, 560], [268, 533]],
+                }]),
+                examples: Some(vec![Example {
+                    id: 1,
+                    value: "地窖里很冷。".to_string(),
+                    translations: vec![Translation {
+                        language: "en".to_string(),
+                        value: "It is cold in the cellar.".to_string(),
+                    }],
+                }]),
+                tags: Some(vec!["hsk-2-6".to_string()]),
+            }],
+        }
+    }
+
+    #[test]
+    fn should_match_golden_files() {
+        let schema_directory = env::current_dir().unwrap().join("schema");
+
+        if env::var("UPDATE_SCHEMA").is_ok() {
+            export_schema(&schema_directory).unwrap();
+        }
+
+        let schema = get_json_schema().unwrap();
+        let expected = fs::read_to_string(schema_directory.join(JSON_SCHEMA_FILE_NAME)).unwrap();
+        let actual = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+        assert!(
+            actual == expected,
+            "schema/{} is out of date, run `UPDATE_SCHEMA=1 cargo test` and commit the result",
+            JSON_SCHEMA_FILE_NAME
+        );
+
+        let expected =
+            fs::read_to_string(schema_directory.join(TYPESCRIPT_DEFINITIONS_FILE_NAME)).unwrap();
+        let actual = get_typescript_definitions(&schema);
+        assert!(
+            actual == expected,
+            "schema/{} is out of date, run `UPDATE_SCHEMA=1 cargo test` and commit the result",
+            TYPESCRIPT_DEFINITIONS_FILE_NAME
+        );
+    }
+
+    #[test]
+    fn should_validate_serialized_group() {
+        let schema_directory = env::current_dir().unwrap().join("schema");
+        let bytes = fs::read(schema_directory.join(JSON_SCHEMA_FILE_NAME)).unwrap();
+        let schema: Value = serde_json::from_slice(&bytes).unwrap();
+        let value = serde_json::to_value(get_full_group()).unwrap();
+
+        let errors = validate(&value, &schema, &schema, "$");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn should_report_unknown_fields() {
+        let schema = get_json_schema().unwrap();
+        let mut value = serde_json::to_value(get_full_group()).unwrap();
+        value["details"][0]["unknown"] = Value::Bool(true);
+        value["details"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("traditional");
+
+        let errors = validate(&value, &schema, &schema, "$");
+        assert_eq!(
+            errors,
+            vec![
+                "$.details[0]: missing \"traditional\"",
+                "$.details[0].unknown: not in schema"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_convert_to_typescript() {
+        let schema = get_json_schema().unwrap();
+        let definitions = get_typescript_definitions(&schema);
+
+        assert!(definitions.contains("export interface Group {\n"));
+        assert!(definitions.contains("    details: Detail[];\n"));
+        assert!(definitions.contains("    variant?: Variant;\n"));
+        assert!(definitions.contains("    context?: string[];\n"));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::models::Stroke;
+use crate::utils::escape_html::escape_html;
+use crate::utils::get_entry_path::get_safe_file_name;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// The graphics data has the y axis pointing up with the baseline at 900.
+static FLIP_Y: &str = "translate(0, 900) scale(1, -1)";
+static SECONDS_PER_UNIT: f64 = 1.0 / 1200.0;
+static MINIMUM_DURATION: f64 = 0.3;
+static PAUSE: f64 = 0.2;
+
+fn get_median_length(median: &[[i32; 2]]) -> f64 {
+    median
+        .windows(2)
+        .map(|pr| {
+            let dx = (pr[1][0] - pr[0][0]) as f64;
+            let dy = (pr[1][1] - pr[0][1]) as f64;
+            (dx * dx + dy * dy).sqrt()
+        })
+        .sum()
+}
+
+fn get_median_path(median: &[[i32; 2]]) -> String {
+    let points: Vec<String> = median
+        .iter()
+        .map(|pr| format!("{} {}", pr[0], pr[1]))
+        .collect();
+
+    format!("M {}", points.join(" L "))
+}
+
+/// Every stroke filled, with its number next to where it starts.
+pub fn render_static_svg(strokes: &[Stroke]) -> String {
+    let mut result = String::from(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 1024 1024\" class=\"stroke-order\">\n",
+    );
+
+    result = result + &format!("<g transform=\"{}\" fill=\"#333\">\n", FLIP_Y);
+
+    for stroke in strokes {
+        result = result + &format!("<path d=\"{}\"/>\n", escape_html(&stroke.path));
+    }
+
+    result = result + "</g>\n<g fill=\"#c00\" font-size=\"64\" font-family=\"sans-serif\">\n";
+
+    for (index, stroke) in strokes.iter().enumerate() {
+        if let Some([x, y]) = stroke.median.first() {
+            result =
+                result + &format!("<text x=\"{}\" y=\"{}\">{}</text>\n", x, 900 - y, index + 1);
+        }
+    }
+
+    result + "</g>\n</svg>\n"
+}
+
+/// Draws the strokes one after the other by sweeping a thick line along each
+/// median, clipped to the stroke outline. `id` keeps the clip paths of several
+/// diagrams on one page apart.
+pub fn render_animated_svg(strokes: &[Stroke], id: &str) -> String {
+    let mut result = String::from(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 1024 1024\" class=\"stroke-order\">\n",
+    );
+
+    result = result
+        + "<style>@keyframes draw { to { stroke-dashoffset: 0; } }\n\
+           .median { fill: none; stroke: #333; stroke-width: 128; \
+           stroke-linecap: round; stroke-linejoin: round; }</style>\n";
+    result = result + &format!("<g transform=\"{}\">\n", FLIP_Y);
+
+    for stroke in strokes {
+        result = result
+            + &format!(
+                "<path d=\"{}\" fill=\"#ddd\"/>\n",
+                escape_html(&stroke.path)
+            );
+    }
+
+    let mut delay = 0.0;
+
+    for (index, stroke) in strokes.iter().enumerate() {
+        let length = get_median_length(&stroke.median).ceil() + 128.0;
+        let duration = (length * SECONDS_PER_UNIT).max(MINIMUM_DURATION);
+
+        result = result
+            + &format!(
+                "<clipPath id=\"{}-stroke-{}\"><path d=\"{}\"/></clipPath>\n",
+                escape_html(id),
+                index,
+                escape_html(&stroke.path)
+            );
+        result = result
+            + &format!(
+                "<path class=\"median\" clip-path=\"url(#{}-stroke-{})\" d=\"{}\" \
+                 style=\"stroke-dasharray: {} {}; stroke-dashoffset: {}; \
+                 animation: draw {:.2}s linear {:.2}s forwards;\"/>\n",
+                escape_html(id),
+                index,
+                get_median_path(&stroke.median),
+                length,
+                length,
+                length,
+                duration,
+                delay
+            );
+
+        delay = delay + duration + PAUSE;
+    }
+
+    result + "</g>\n</svg>\n"
+}
+
+/// Writes `<character>.svg` and `<character>-animated.svg` for every character
+/// with graphics into `output_directory`, returning how many were written.
+pub fn export_stroke_order_svgs(
+    graphics: &HashMap<String, Vec<Stroke>>,
+    output_directory: &Path,
+) -> Result<usize, Box<dyn Error>> {
+    fs::create_dir_all(output_directory)?;
+
+    for (character, strokes) in graphics {
+        let name = get_safe_file_name(character);
+
+        fs::write(
+            output_directory.join(format!("{}.svg", name)),
+            render_static_svg(strokes),
+        )?;
+        fs::write(
+            output_directory.join(format!("{}-animated.svg", name)),
+            render_animated_svg(strokes, &name),
+        )?;
+    }
+
+    Ok(graphics.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    fn get_strokes() -> Vec<Stroke> {
+        vec![
+            Stroke {
+                path: "M 100 500 L 900 500 L 900 400 L 100 400 Z".to_string(),
+                median: vec![[100, 450], [900, 450]],
+            },
+            Stroke {
+                path: "M 450 800 L 550 800 L 550 100 L 450 100 Z".to_string(),
+                median: vec![[500, 800], [500, 500], [500, 100]],
+            },
+        ]
+    }
+
+    #[test]
+    fn should_render_static_svg() {
+        let result = render_static_svg(&get_strokes());
+
+        assert_eq!(result.matches("<path ").count(), 2);
+        assert!(result.contains("<text x=\"100\" y=\"450\">1</text>"));
+        assert!(result.contains("<text x=\"500\" y=\"100\">2</text>"));
+    }
+
+    #[test]
+    fn should_render_animated_svg() {
+        let result = render_animated_svg(&get_strokes(), "十");
+
+        assert!(result.contains("<clipPath id=\"十-stroke-1\">"));
+        assert!(result.contains("clip-path=\"url(#十-stroke-1)\""));
+
+        assert!(result.contains("d=\"M 500 800 L 500 500 L 500 100\""));
+        assert!(result.contains("stroke-dasharray: 928 928"));
+        assert!(result.contains("animation: draw 0.77s linear 0.00s forwards;"));
+        assert!(result.contains("animation: draw 0.69s linear 0.97s forwards;"));
+        assert_eq!(get_median_length(&[[0, 0], [3, 4], [3, 10]]), 11.0);
+    }
+
+    #[test]
+    fn should_export_stroke_order_svgs() {
+        let output_directory = env::temp_dir().join("refined-cedict-json-strokes");
+        let mut graphics = HashMap::new();
+        graphics.insert("十".to_string(), get_strokes());
+
+        let count = export_stroke_order_svgs(&graphics, &output_directory).unwrap();
+        let exists = output_directory.join("十.svg").exists()
+            && output_directory.join("十-animated.svg").exists();
+        fs::remove_dir_all(&output_directory).unwrap();
+
+        assert_eq!(count, 1);
+        assert!(exists);
+    }
+}
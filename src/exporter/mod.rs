@@ -0,0 +1,4 @@
+pub mod export_html_site;
+pub mod export_json_files;
+pub mod export_schema;
+pub mod export_stroke_order_svgs;
@@ -1,17 +1,56 @@
 mod api;
 mod customReader;
+mod exporter;
 mod models;
 mod refiner;
+mod search;
+mod server;
+mod text;
+use crate::api::cached_fetcher::CachedFetcher;
 use crate::api::download_cedict;
-use crate::api::get_character_decomposition_from_hanzicraft::get_character_decomposition_from_hanzicraft;
-use crate::api::get_radicals_from_wikipedia::get_radicals_from_wikipedia;
+use crate::api::fetcher::get_fetcher;
+use crate::api::providers::DecompositionProvider;
+use crate::api::providers::Hanzicraft;
+use crate::api::providers::Nihongo;
+use crate::api::providers::StrokeCountProvider;
+use crate::api::providers::Strokeorder;
+use crate::api::providers::Unihan;
+use crate::api::providers::Wiktionary;
+use crate::api::stroke_count_resolver::StrokeCountResolver;
 use crate::models::*;
+use crate::refiner::classify_components::classify_components;
+use crate::refiner::classify_components::get_phonetic_series;
+use crate::refiner::classify_components::get_readings;
+use crate::search::similar_characters::SimilarityIndex;
+use crate::server::dictionary::Dictionary;
+use crate::server::serve::serve;
+use crate::text::annotate::render_plain_text;
+use crate::text::annotate::render_ruby_html;
+use crate::text::annotate::Annotator;
+use crate::text::coverage::get_coverage_report;
+use crate::text::coverage::render_coverage_report;
+use crate::text::segmenter::Segmenter;
 mod utils;
 use crate::customReader::custom_reader::BufReader;
+use crate::exporter::export_html_site::export_html_site;
+use crate::exporter::export_json_files::export_json_files;
+use crate::exporter::export_schema::export_schema;
+use crate::exporter::export_stroke_order_svgs::export_stroke_order_svgs;
+use crate::utils::checkpoint::Checkpoint;
+use crate::utils::get_component_table_from_file::get_component_table_from_file;
+use crate::utils::get_decomposition_from_file::get_decomposition_from_file;
 use crate::utils::get_descriptors_from_file::get_descriptors_from_file;
+use crate::utils::get_hsk_levels_from_directory::get_hsk_levels_from_directory;
+use crate::utils::get_ids_from_file::get_ids_from_file;
+use crate::utils::get_radicals_from_file::get_radicals;
+use crate::utils::get_stroke_graphics_from_file::get_stroke_graphics_from_file;
+use crate::utils::get_unihan_from_directory::get_unihan_from_directory;
+use crate::utils::kangxi_radicals::save_kangxi_radicals_to_file;
 use log::{debug, info};
 use refiner::refine_records::refine_records;
 use refiner::*;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::error::Error;
@@ -29,51 +68,20 @@ use utils::*;
 #[macro_use]
 extern crate log;
 
+const SERVE_ADDRESS: &str = "127.0.0.1:8080";
+
 pub fn update_descriptor(assets_directory: &Path) -> Result<(), Box<dyn Error>> {
     let mut descriptors = get_descriptors_from_file(&assets_directory.join("descriptor.txt"))?;
 
-    let hsk21 = get_lines_from_file(&assets_directory.join("hsk-version-2-1.txt"))?;
-    let hsk22 = get_lines_from_file(&assets_directory.join("hsk-version-2-2.txt"))?;
-    let hsk23 = get_lines_from_file(&assets_directory.join("hsk-version-2-3.txt"))?;
-    let hsk24 = get_lines_from_file(&assets_directory.join("hsk-version-2-4.txt"))?;
-    let hsk25 = get_lines_from_file(&assets_directory.join("hsk-version-2-5.txt"))?;
-    let hsk26 = get_lines_from_file(&assets_directory.join("hsk-version-2-6.txt"))?;
-    let hsk31 = get_row_from_file(&assets_directory.join("hsk-version-3-1.txt"), 1, "\t")?;
-    let hsk32 = get_row_from_file(&assets_directory.join("hsk-version-3-2.txt"), 1, "\t")?;
-    let hsk33 = get_row_from_file(&assets_directory.join("hsk-version-3-3.txt"), 1, "\t")?;
-    let hsk34 = get_row_from_file(&assets_directory.join("hsk-version-3-4.txt"), 1, "\t")?;
-    let hsk35 = get_row_from_file(&assets_directory.join("hsk-version-3-5.txt"), 1, "\t")?;
-    let hsk36 = get_row_from_file(&assets_directory.join("hsk-version-3-6.txt"), 1, "\t")?;
-    let hsk37 = get_row_from_file(&assets_directory.join("hsk-version-3-7.txt"), 1, "\t")?;
-
-    const attributes: &'static [&'static str] = &[
-        "hsk-2-1", "hsk-2-2", "hsk-2-3", "hsk-2-4", "hsk-2-5", "hsk-2-6", "hsk-3-1", "hsk-3-2",
-        "hsk-3-3", "hsk-3-4", "hsk-3-5", "hsk-3-6", "hsk-3-7",
-    ];
+    let hsk_levels = get_hsk_levels_from_directory(assets_directory)?;
 
     for (key, descriptor) in descriptors.iter_mut() {
         let mut temp = descriptor.tags.clone().unwrap_or_default();
         let key = &descriptor.simplified;
 
-        let results = vec![
-            hsk21.get(key),
-            hsk22.get(key),
-            hsk23.get(key),
-            hsk24.get(key),
-            hsk25.get(key),
-            hsk26.get(key),
-            hsk31.get(key),
-            hsk32.get(key),
-            hsk33.get(key),
-            hsk34.get(key),
-            hsk35.get(key),
-            hsk36.get(key),
-            hsk37.get(key),
-        ];
-
-        for (result, attribute) in results.iter().zip(attributes) {
-            if let Some(_) = result {
-                temp.push(attribute.to_string());
+        for hsk_level in &hsk_levels {
+            if hsk_level.words.contains(key) {
+                temp.push(hsk_level.tag.to_string());
             }
         }
 
@@ -113,6 +121,14 @@ pub fn download_cedict_and_save_to_disk(
     Ok(())
 }
 
+fn read_resume() -> Result<bool, Box<dyn Error>> {
+    let mut answer = String::from("");
+    println!("Resume from the last checkpoint? (y/n)");
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
     let current_directory = env::current_dir()?;
@@ -123,14 +139,24 @@ fn main() -> Result<(), Box<dyn Error>> {
         let mut command = String::from("");
         println!("Commands");
         println!("1. Export characters");
-        println!("2. Import stroke count from wikipedia");
+        println!("2. Import stroke counts from Unihan and the web, with conflict and misses files");
         println!("3. Export basic json version of cedict_ts.u8");
         println!("4. Export refined json cedict_ts.u8");
         println!("5. Export refined phrases to separate json files");
         println!("6. Export pinyins");
-        println!("7. Import radicals");
+        println!("7. Export built-in Kangxi radicals to assets/radicals.txt");
         println!("8. Extract meanings");
-        println!("9. Exit");
+        println!("10. Export refined phrases to static html site");
+        println!("11. Export json schema and typescript definitions");
+        println!("12. Serve refined dictionary over http (serve)");
+        println!("13. Segment text into dictionary words (segment)");
+        println!("14. Annotate text with pinyin as html, json or text (annotate)");
+        println!("15. Report hsk coverage and difficulty of a text file (coverage)");
+        println!("16. Export phonetic series and component roles (phonetic-series)");
+        println!("17. List visually similar characters (similar)");
+        println!("18. Export Unihan data from assets/unihan (unihan)");
+        println!("19. Export static and animated stroke order svgs (stroke-svg)");
+        println!("9. Exit (exit)");
         io::stdin().read_line(&mut command)?;
         command = command.trim().to_owned();
 
@@ -138,6 +164,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let cache_list_path = &current_directory.join("cache-list.json");
         let cache_dict_path = &current_directory.join("cache-dict.json");
         let cache_refined_path = &current_directory.join("cache-refined.json");
+        let cache_http_directory = &current_directory.join("cache-http");
 
         if !cedict_ts_path.exists() {
             debug!("Could not find cedict_ts.u8");
@@ -160,51 +187,129 @@ fn main() -> Result<(), Box<dyn Error>> {
             }
             "2" => {
                 let output_path = &current_directory.join("stroke-order.txt");
-                let lines = import_stroke_order(output_path)?;
+                let partial_path = &current_directory.join("stroke-order.txt.partial");
+                let retry_path = &current_directory.join("stroke-order.retry.txt");
+                let conflicts_path = &current_directory.join("stroke-order.conflicts.txt");
+                let misses_path = &current_directory.join("stroke-order.misses.txt");
+                let resume = read_resume()?;
+                let mut checkpoint = Checkpoint::open(partial_path, retry_path, resume)?;
+                let mut conflict_writer = LineWriter::new(
+                    OpenOptions::new()
+                        .create(true)
+                        .write(true)
+                        .append(resume)
+                        .truncate(!resume)
+                        .open(conflicts_path)?,
+                );
+
+                let get_cached_fetcher =
+                    || CachedFetcher::new(get_fetcher(), cache_http_directory.clone());
+                let mut providers: Vec<(String, Box<dyn StrokeCountProvider>)> = vec![
+                    (
+                        "wiktionary".to_string(),
+                        Box::new(Wiktionary {
+                            fetcher: get_cached_fetcher(),
+                        }),
+                    ),
+                    (
+                        "nihongo".to_string(),
+                        Box::new(Nihongo {
+                            fetcher: get_cached_fetcher(),
+                        }),
+                    ),
+                    (
+                        "strokeorder".to_string(),
+                        Box::new(Strokeorder {
+                            fetcher: get_cached_fetcher(),
+                        }),
+                    ),
+                ];
+
+                let unihan_directory = assets_directory.join("unihan");
+
+                if unihan_directory.exists() {
+                    let records = get_unihan_from_directory(&unihan_directory, &HashSet::new())?;
+                    providers.insert(0, ("unihan".to_string(), Box::new(Unihan { records })));
+                }
 
-                let file = File::create(output_path)?;
-                let mut line_writer = LineWriter::new(file);
+                let decomposition_path = assets_directory.join("character-decomposition.txt");
+                let table = if decomposition_path.exists() {
+                    get_component_table_from_file(&decomposition_path)?
+                } else {
+                    ComponentTable::default()
+                };
 
-                for line in lines {
-                    line_writer.write_all(line.as_bytes())?;
+                let stroke_order_path = assets_directory.join("stroke-order.txt");
+                let stroke_counts = if stroke_order_path.exists() {
+                    get_stroke_order_map(&stroke_order_path)?
+                } else {
+                    HashMap::new()
+                };
+
+                // Unihan is offline and authoritative, the web sources are only
+                // asked on a miss unless STROKE_COUNT_CROSS_CHECK is set
+                let cross_check = env::var("STROKE_COUNT_CROSS_CHECK").is_ok();
+                let mut resolver = StrokeCountResolver::new(providers, table, stroke_counts)
+                    .with_trusted_sources(&["unihan"], cross_check);
+
+                // Misses are not checkpointed, so a resumed run asks for them again
+                let mut miss_writer = LineWriter::new(File::create(misses_path)?);
+
+                let miss_count = import_stroke_order(
+                    output_path,
+                    &mut resolver,
+                    &mut checkpoint,
+                    &mut conflict_writer,
+                    &mut miss_writer,
+                )?;
+
+                if miss_count > 0 {
+                    println!(
+                        "{} characters could not be resolved, see {:?}.",
+                        miss_count, misses_path
+                    );
+                }
+
+                if checkpoint.retry_count == 0 {
+                    fs::rename(partial_path, output_path)?;
+                } else {
+                    println!(
+                        "{} characters failed, see {:?}. Run again and resume to retry them.",
+                        checkpoint.retry_count, retry_path
+                    );
                 }
             }
             "3" => {
                 let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
                 let single_characters = get_single_characters(&list);
-                let mut processed: HashSet<String> = HashSet::new();
-                let mut lines: Vec<String> = Vec::new();
-                let file_path = current_directory.join("character-decomposition.txt");
-
-                if file_path.exists() {
-                    let reader = BufReader::open(&file_path)?;
-
-                    for line in reader {
-                        let line = line?;
-                        let radical = line.split(";").next().unwrap().to_string();
-                        lines.push(line.to_string());
-                        processed.insert(radical);
-                    }
-                }
-
-                let file = File::create(file_path)?;
-                let mut line_writer = LineWriter::new(file);
-
-                for line in lines {
-                    line_writer.write_all(line.as_bytes())?;
-                }
+                let file_path = &current_directory.join("character-decomposition.txt");
+                let retry_path = &current_directory.join("character-decomposition.retry.txt");
+                let mut checkpoint = Checkpoint::open(file_path, retry_path, read_resume()?)?;
+                let provider = Hanzicraft {
+                    fetcher: CachedFetcher::new(get_fetcher(), cache_http_directory.clone()),
+                };
 
                 for character in single_characters {
-                    if processed.contains(&character.to_string()) {
+                    let character = character.to_string();
+
+                    if checkpoint.contains(&character) {
                         continue;
                     }
 
                     info!("Processing: {}", character);
-                    let decomposition =
-                        get_character_decomposition_from_hanzicraft(&character.to_string())?;
 
-                    let line = format!("{}\n", decomposition);
-                    line_writer.write_all(line.as_bytes())?;
+                    match provider.get_decomposition(&character) {
+                        Ok(decomposition) => checkpoint
+                            .write_line(&decomposition.unwrap_or_else(|| character.to_owned()))?,
+                        Err(error) => checkpoint.add_retry(&character, error.as_ref())?,
+                    }
+                }
+
+                if checkpoint.retry_count > 0 {
+                    println!(
+                        "{} characters failed, see {:?}. Run again and resume to retry them.",
+                        checkpoint.retry_count, retry_path
+                    );
                 }
             }
             "4" => {
@@ -246,12 +351,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                     &assets_directory,
                 )?;
 
-                for refined_record in refined_records {
-                    let file = File::create(
-                        public_directory.join(format!("{}.json", &refined_record.simplified)),
-                    )?;
-                    serde_json::to_writer_pretty(file, &refined_record)?;
-                }
+                export_json_files(&refined_records, &public_directory)?;
             }
             "6" => {
                 let file_path = current_directory.join("extracted.txt");
@@ -262,17 +362,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 }
             }
             "7" => {
-                let file = File::create(assets_directory.join("radicals.txt"))?;
-                let mut line_writer = LineWriter::new(file);
-                let radicals = get_radicals_from_wikipedia()?;
-
-                for radical in radicals {
-                    let line = format!(
-                        "{};{};{}\n",
-                        radical.value, radical.stroke_count, radical.pinyin
-                    );
-                    line_writer.write_all(line.as_bytes())?;
-                }
+                save_kangxi_radicals_to_file(&assets_directory.join("radicals.txt"))?;
 
                 // let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
 
@@ -297,22 +387,15 @@ fn main() -> Result<(), Box<dyn Error>> {
                 // }
             }
             "8" => {
-                let mut refined_records: Vec<Group> = Vec::new();
-
-                if cache_refined_path.exists() {
-                    refined_records = get_cached_refined_records(cache_refined_path)?;
-                } else {
-                    let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
-
-                    let grouped_records =
-                        get_group_ce_records_by_simplified(&list, cache_dict_path)?;
-                    refined_records = refine_records(
-                        grouped_records,
-                        &current_directory,
-                        &public_directory,
-                        &assets_directory,
-                    )?;
-                }
+                let refined_records = try_get_refined_records(
+                    cedict_ts_path,
+                    cache_list_path,
+                    cache_dict_path,
+                    cache_refined_path,
+                    &current_directory,
+                    &public_directory,
+                    &assets_directory,
+                )?;
 
                 let file = File::create(current_directory.join("unmapped.txt"))?;
                 let mut line_writer = LineWriter::new(file);
@@ -351,10 +434,202 @@ fn main() -> Result<(), Box<dyn Error>> {
                     }
                 }
             }
-            "9" => {
+            "9" | "exit" => {
                 debug!("Quit");
                 break;
             }
+            "10" => {
+                let refined_records = try_get_refined_records(
+                    cedict_ts_path,
+                    cache_list_path,
+                    cache_dict_path,
+                    cache_refined_path,
+                    &current_directory,
+                    &public_directory,
+                    &assets_directory,
+                )?;
+                let hsk_levels = get_hsk_levels_from_directory(&assets_directory)?;
+
+                export_html_site(&refined_records, &hsk_levels, &public_directory)?;
+            }
+            "11" => {
+                export_schema(&public_directory)?;
+            }
+            "12" | "serve" => {
+                let refined_records = try_get_refined_records(
+                    cedict_ts_path,
+                    cache_list_path,
+                    cache_dict_path,
+                    cache_refined_path,
+                    &current_directory,
+                    &public_directory,
+                    &assets_directory,
+                )?;
+                let radicals = get_radicals(&assets_directory)?;
+                let mut decompositions = get_decomposition_from_file(
+                    &assets_directory.join("character-decomposition.txt"),
+                    &radicals,
+                )?;
+                let ids_path = assets_directory.join("ids.txt");
+
+                if ids_path.exists() {
+                    for (character, ids) in get_ids_from_file(&ids_path)? {
+                        decompositions
+                            .entry(character)
+                            .or_insert_with(|| Decomposition {
+                                radical: Vec::new(),
+                                graphical: Vec::new(),
+                                ids: None,
+                            })
+                            .ids = Some(ids);
+                    }
+                }
+
+                let component_table = get_component_table_from_file(
+                    &assets_directory.join("character-decomposition.txt"),
+                )?;
+                let hsk_levels = get_hsk_levels_from_directory(&assets_directory)?;
+
+                let dictionary = Dictionary::new(
+                    refined_records,
+                    decompositions,
+                    component_table,
+                    &hsk_levels,
+                );
+                serve(&dictionary, SERVE_ADDRESS)?;
+            }
+            "13" | "segment" => {
+                let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
+                let segmenter = Segmenter::new(&list);
+
+                let mut text = String::from("");
+                println!("Text:");
+                io::stdin().read_line(&mut text)?;
+
+                let tokens = segmenter.segment(text.trim());
+                println!("{}", serde_json::to_string_pretty(&tokens)?);
+            }
+            "14" | "annotate" => {
+                let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
+                let temp = assets_directory.join("pinyin-map.txt");
+                let pinyins_map = get_pinyins_map(temp.to_str().unwrap())?;
+                let annotator = Annotator::new(&list, pinyins_map);
+
+                let mut format = String::from("");
+                println!("Format (html, json, text):");
+                io::stdin().read_line(&mut format)?;
+
+                let mut text = String::from("");
+                println!("Text:");
+                io::stdin().read_line(&mut text)?;
+
+                let tokens = annotator.annotate(text.trim());
+
+                match format.trim() {
+                    "html" => println!("{}", render_ruby_html(&tokens)),
+                    "json" => println!("{}", serde_json::to_string_pretty(&tokens)?),
+                    _ => println!("{}", render_plain_text(&tokens)),
+                }
+            }
+            "15" | "coverage" => {
+                let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
+                let temp = assets_directory.join("pinyin-map.txt");
+                let pinyins_map = get_pinyins_map(temp.to_str().unwrap())?;
+                let annotator = Annotator::new(&list, pinyins_map);
+                let hsk_levels = get_hsk_levels_from_directory(&assets_directory)?;
+
+                let mut file_path = String::from("");
+                println!("Text file:");
+                io::stdin().read_line(&mut file_path)?;
+
+                let text = fs::read_to_string(current_directory.join(file_path.trim()))?;
+                let tokens = annotator.annotate(&text);
+                let report = get_coverage_report(&tokens, &hsk_levels);
+                println!("{}", render_coverage_report(&report));
+            }
+            "16" | "phonetic-series" => {
+                let refined_records = try_get_refined_records(
+                    cedict_ts_path,
+                    cache_list_path,
+                    cache_dict_path,
+                    cache_refined_path,
+                    &current_directory,
+                    &public_directory,
+                    &assets_directory,
+                )?;
+                let radicals = get_radicals(&assets_directory)?;
+                let decomposition_path = assets_directory.join("character-decomposition.txt");
+                let decompositions = get_decomposition_from_file(&decomposition_path, &radicals)?;
+                let component_table = get_component_table_from_file(&decomposition_path)?;
+                let readings = get_readings(&refined_records);
+
+                let mut roles: BTreeMap<&String, Vec<ClassifiedComponent>> = BTreeMap::new();
+
+                for character in component_table.components.keys() {
+                    let components = classify_components(
+                        character,
+                        &component_table,
+                        &decompositions,
+                        &readings,
+                    );
+
+                    if !components.is_empty() {
+                        roles.insert(character, components);
+                    }
+                }
+
+                let series = get_phonetic_series(&component_table, &decompositions, &readings);
+
+                fs::create_dir_all(&public_directory)?;
+                let file = File::create(public_directory.join("phonetic-series.json"))?;
+                serde_json::to_writer_pretty(BufWriter::new(file), &series)?;
+                let file = File::create(public_directory.join("component-roles.json"))?;
+                serde_json::to_writer_pretty(BufWriter::new(file), &roles)?;
+            }
+            "17" | "similar" => {
+                let decompositions = get_decomposition_from_file(
+                    &assets_directory.join("character-decomposition.txt"),
+                    &HashMap::new(),
+                )?;
+                let stroke_order_map =
+                    get_stroke_order_map(&assets_directory.join("stroke-order.txt"))?;
+                let similarity_index = SimilarityIndex::new(&decompositions, stroke_order_map);
+
+                let mut character = String::from("");
+                println!("Character:");
+                io::stdin().read_line(&mut character)?;
+
+                for item in similarity_index.find_similar(character.trim(), 10) {
+                    println!("{} {:.2}", item.character, item.score);
+                }
+            }
+            "18" | "unihan" => {
+                let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
+                let single_characters = get_single_characters(&list);
+                let records = get_unihan_from_directory(
+                    &assets_directory.join("unihan"),
+                    &single_characters,
+                )?;
+                let records: BTreeMap<String, UnihanRecord> = records.into_iter().collect();
+
+                fs::create_dir_all(&public_directory)?;
+                let file = File::create(public_directory.join("unihan.json"))?;
+                let buffer_writer = BufWriter::new(file);
+                serde_json::to_writer_pretty(buffer_writer, &records)?;
+
+                println!(
+                    "Exported Unihan data of {} out of {} characters",
+                    records.len(),
+                    single_characters.len()
+                );
+            }
+            "19" | "stroke-svg" => {
+                let graphics =
+                    get_stroke_graphics_from_file(&assets_directory.join("graphics.txt"))?;
+                let count = export_stroke_order_svgs(&graphics, &public_directory.join("strokes"))?;
+
+                println!("Exported stroke order diagrams of {} characters", count);
+            }
             _ => {
                 info!("Could not find command.");
             }
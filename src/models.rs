@@ -1,8 +1,13 @@
 use core::cmp::Ordering;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::HashSet;
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Default, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Meaning {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,10 +24,14 @@ pub struct Meaning {
     pub wade_giles_pinyin: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pinyin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Classifier {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<String>,
     pub simplified: String,
     pub traditional: String,
     pub wade_giles_pinyin: String,
@@ -37,15 +46,17 @@ pub struct Abbreviation {
     pub wade_giles_pinyin: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Default, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Pronunciation {
     pub pinyin: String,
     pub wade_giles_pinyin: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub other: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jyutping: Option<Vec<String>>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Radical {
     pub value: String,
     pub stroke_count: u8,
@@ -53,14 +64,19 @@ pub struct Radical {
     pub pinyin: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Decomposition {
     pub radical: Vec<Radical>,
     pub graphical: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Variant {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<String>,
+
     pub simplified: String,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -70,8 +86,10 @@ pub struct Variant {
     pub wade_giles_pinyin: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Default, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Detail {
+    pub id: String,
+
     pub pronunciation: Vec<Pronunciation>,
 
     pub simplified: String,
@@ -95,11 +113,43 @@ pub struct Detail {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub decomposition: Option<Decomposition>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similar: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strokes: Option<Vec<Stroke>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<Example>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+/// One stroke of a character in a 1024 unit box with the y axis pointing up,
+/// as in the Make Me a Hanzi graphics data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Stroke {
+    pub path: String,
+    pub median: Vec<[i32; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Translation {
+    /// ISO 639-1 code, as on `Meaning`.
+    pub language: String,
+    pub value: String,
+}
+
+/// A Tatoeba sentence using the word, with its translations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Example {
+    pub id: u32,
+    pub value: String,
+    pub translations: Vec<Translation>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Group {
     pub simplified: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -107,6 +157,13 @@ pub struct Group {
     pub details: Vec<Detail>,
 }
 
+/// The gloss language of CC-CEDICT.
+pub static DEFAULT_LANGUAGE: &str = "en";
+
+fn get_default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CERecord {
     pub line_number: u32,
@@ -115,6 +172,17 @@ pub struct CERecord {
     pub traditional: String,
     pub wade_giles_pinyin: String,
     pub meanings: Vec<String>,
+    /// ISO 639-1 code of the glosses in `meanings`.
+    #[serde(default = "get_default_language")]
+    pub language: String,
+}
+
+/// Jyutping readings keyed by detail id, and the CC-Canto entries with
+/// definitions of their own.
+#[derive(Clone, Default)]
+pub struct Cantonese {
+    pub readings: HashMap<String, Vec<String>>,
+    pub records: Vec<CERecord>,
 }
 
 #[derive(Clone, Hash, Serialize, Deserialize)]
@@ -126,6 +194,119 @@ pub struct Descriptor {
     pub tags: Option<Vec<String>>,
 }
 
+#[derive(Clone)]
+pub struct HskLevel {
+    pub tag: String,
+    pub version: u8,
+    pub level: u8,
+    pub words: HashSet<String>,
+}
+
+#[derive(Clone, Default)]
+pub struct ComponentTable {
+    pub components: HashMap<String, Vec<Option<String>>>,
+    pub meanings: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+    Decomposed,
+    Primitive,
+    Unavailable,
+    Cycle,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecompositionTree {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meaning: Option<String>,
+    pub status: ComponentStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub components: Vec<DecompositionTree>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdsOperator {
+    LeftToRight,
+    AboveToBelow,
+    LeftToMiddleAndRight,
+    AboveToMiddleAndBelow,
+    FullSurround,
+    SurroundFromAbove,
+    SurroundFromBelow,
+    SurroundFromLeft,
+    SurroundFromUpperLeft,
+    SurroundFromUpperRight,
+    SurroundFromLowerLeft,
+    Overlaid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum IdsNode {
+    Component(String),
+    Composition {
+        operator: IdsOperator,
+        components: Vec<IdsNode>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentRole {
+    Semantic,
+    Phonetic,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifiedComponent {
+    pub value: String,
+    pub role: ComponentRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesMember {
+    pub character: String,
+    pub readings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhoneticSeries {
+    pub phonetic: String,
+    pub readings: Vec<String>,
+    pub members: Vec<SeriesMember>,
+}
+
+/// A kRSUnicode value such as "120'.3": the Kangxi radical number, whether the
+/// simplified form of the radical is meant, and the strokes beyond it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RadicalStroke {
+    pub radical: u8,
+    pub simplified: bool,
+    pub additional_strokes: i8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnihanRecord {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_strokes: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radical_stroke: Option<RadicalStroke>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub mandarin: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub simplified_variants: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub traditional_variants: Vec<String>,
+}
+
 #[derive(Clone, Hash, Serialize, Deserialize)]
 pub struct PinyinMap {
     pub pinyin: String,
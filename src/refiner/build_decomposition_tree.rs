@@ -0,0 +1,127 @@
+use crate::models::ComponentStatus;
+use crate::models::ComponentTable;
+use crate::models::DecompositionTree;
+
+/// Decomposes `character` through its immediate components until primitives
+/// are reached. A component the data has no glyph for becomes an unavailable
+/// leaf, and one that reappears among its own ancestors is cut off as a cycle.
+pub fn build_decomposition_tree(character: &str, table: &ComponentTable) -> DecompositionTree {
+    let mut ancestors: Vec<String> = Vec::new();
+    build_node(Some(character), table, &mut ancestors)
+}
+
+fn build_node(
+    value: Option<&str>,
+    table: &ComponentTable,
+    ancestors: &mut Vec<String>,
+) -> DecompositionTree {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            return DecompositionTree {
+                value: None,
+                meaning: None,
+                status: ComponentStatus::Unavailable,
+                components: Vec::new(),
+            }
+        }
+    };
+
+    let mut node = DecompositionTree {
+        value: Some(value.to_string()),
+        meaning: table.meanings.get(value).cloned(),
+        status: ComponentStatus::Primitive,
+        components: Vec::new(),
+    };
+
+    if ancestors.iter().any(|pr| pr == value) {
+        node.status = ComponentStatus::Cycle;
+        return node;
+    }
+
+    let components = match table.components.get(value) {
+        Some(components) => components,
+        None => return node,
+    };
+
+    if components.is_empty() || components.iter().all(|pr| pr.as_deref() == Some(value)) {
+        return node;
+    }
+
+    ancestors.push(value.to_string());
+
+    node.status = ComponentStatus::Decomposed;
+    node.components = components
+        .iter()
+        .map(|pr| build_node(pr.as_deref(), table, ancestors))
+        .collect();
+
+    ancestors.pop();
+
+    node
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_table(items: &[(&str, &[Option<&str>])]) -> ComponentTable {
+        let mut table = ComponentTable::default();
+
+        for (character, components) in items {
+            table.components.insert(
+                character.to_string(),
+                components
+                    .iter()
+                    .map(|pr| pr.map(|pr| pr.to_string()))
+                    .collect(),
+            );
+        }
+
+        table.meanings.insert("女".to_string(), "woman".to_string());
+        table
+    }
+
+    #[test]
+    fn should_build_nested_tree() {
+        let table = get_table(&[
+            ("娶", &[Some("取"), Some("女")]),
+            ("取", &[Some("耳"), Some("又")]),
+            ("耳", &[Some("㔿"), Some("二")]),
+            ("女", &[Some("女")]),
+        ]);
+
+        let tree = build_decomposition_tree("娶", &table);
+        assert_eq!(tree.status, ComponentStatus::Decomposed);
+        assert_eq!(tree.components[0].components[0].components.len(), 2);
+        assert_eq!(tree.components[1].status, ComponentStatus::Primitive);
+        assert_eq!(tree.components[1].meaning.as_deref(), Some("woman"));
+
+        let value = serde_json::to_value(&tree).unwrap();
+        assert_eq!(value["components"][0]["components"][1]["value"], "又");
+        assert_eq!(
+            value["components"][0]["components"][1]["status"],
+            "primitive"
+        );
+        assert!(value["components"][1].get("components").is_none());
+    }
+
+    #[test]
+    fn should_handle_missing_glyphs_and_cycles() {
+        let table = get_table(&[
+            ("塍", &[Some("月"), None]),
+            ("甲", &[Some("乙")]),
+            ("乙", &[Some("甲")]),
+        ]);
+
+        let tree = build_decomposition_tree("塍", &table);
+        assert_eq!(tree.components[1].status, ComponentStatus::Unavailable);
+        assert_eq!(tree.components[1].value, None);
+
+        let tree = build_decomposition_tree("甲", &table);
+        assert_eq!(
+            tree.components[0].components[0].status,
+            ComponentStatus::Cycle
+        );
+    }
+}
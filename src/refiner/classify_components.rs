@@ -0,0 +1,307 @@
+use crate::models::*;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+static INITIALS: &[&str] = &[
+    "zh", "ch", "sh", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "r",
+    "z", "c", "s", "y", "w",
+];
+
+fn normalize_syllable(value: &str) -> String {
+    value
+        .to_lowercase()
+        .replace("u:", "v")
+        .chars()
+        .filter(|pr| pr.is_ascii_alphabetic())
+        .collect()
+}
+
+fn get_final(syllable: &str) -> &str {
+    INITIALS
+        .iter()
+        .find_map(|pr| syllable.strip_prefix(pr))
+        .filter(|pr| !pr.is_empty())
+        .unwrap_or(syllable)
+}
+
+/// Toneless readings of every single character headword, keyed by both its
+/// simplified and traditional form.
+pub fn get_readings(groups: &[Group]) -> HashMap<String, Vec<String>> {
+    let mut readings: HashMap<String, Vec<String>> = HashMap::new();
+
+    for group in groups {
+        for detail in &group.details {
+            if detail.simplified.chars().count() != 1 {
+                continue;
+            }
+
+            for pronunciation in &detail.pronunciation {
+                let syllable = normalize_syllable(&pronunciation.wade_giles_pinyin);
+
+                if syllable.is_empty() {
+                    continue;
+                }
+
+                for headword in &[&detail.simplified, &detail.traditional] {
+                    let items = readings
+                        .entry(headword.to_string())
+                        .or_insert_with(Vec::new);
+
+                    if !items.contains(&syllable) {
+                        items.push(syllable.to_owned());
+                    }
+                }
+            }
+        }
+    }
+
+    readings
+}
+
+/// 2 when the component is read exactly like the character, 1 when they
+/// rhyme, 0 otherwise.
+fn get_phonetic_score(character: &[String], component: &[String]) -> u8 {
+    let mut score = 0;
+
+    for reading in character {
+        for other in component {
+            if reading == other {
+                return 2;
+            }
+
+            if get_final(reading) == get_final(other) {
+                score = 1;
+            }
+        }
+    }
+
+    score
+}
+
+/// Labels the immediate components of a character. The component whose
+/// reading is closest to the character's is taken as phonetic; the others are
+/// semantic when a phonetic was found or when the decomposition data lists
+/// them as a radical, and unknown otherwise.
+pub fn classify_components(
+    character: &str,
+    table: &ComponentTable,
+    decompositions: &HashMap<String, Decomposition>,
+    readings: &HashMap<String, Vec<String>>,
+) -> Vec<ClassifiedComponent> {
+    let components: Vec<&String> = table
+        .components
+        .get(character)
+        .map(|pr| pr.iter().flatten().filter(|pr| *pr != character).collect())
+        .unwrap_or_default();
+
+    let empty: Vec<String> = Vec::new();
+    let character_readings = readings.get(character).unwrap_or(&empty);
+
+    let mut phonetic: Option<(usize, u8)> = None;
+
+    if components.len() > 1 {
+        for (index, component) in components.iter().enumerate() {
+            let component_readings = readings.get(*component).unwrap_or(&empty);
+            let score = get_phonetic_score(character_readings, component_readings);
+
+            if score > 0 && phonetic.map(|pr| score > pr.1).unwrap_or(true) {
+                phonetic = Some((index, score));
+            }
+        }
+    }
+
+    let radicals: Vec<&str> = decompositions
+        .get(character)
+        .map(|pr| pr.radical.iter().map(|pr| pr.value.as_str()).collect())
+        .unwrap_or_default();
+
+    components
+        .iter()
+        .enumerate()
+        .map(|(index, component)| {
+            let role = match phonetic {
+                Some((phonetic, _)) if phonetic == index => ComponentRole::Phonetic,
+                Some(_) => ComponentRole::Semantic,
+                None if radicals.contains(&component.as_str()) => ComponentRole::Semantic,
+                None => ComponentRole::Unknown,
+            };
+
+            ClassifiedComponent {
+                value: component.to_string(),
+                role,
+            }
+        })
+        .collect()
+}
+
+/// Groups characters by the component classified as their phonetic. Series
+/// with a single member are left out since they teach nothing.
+pub fn get_phonetic_series(
+    table: &ComponentTable,
+    decompositions: &HashMap<String, Decomposition>,
+    readings: &HashMap<String, Vec<String>>,
+) -> Vec<PhoneticSeries> {
+    let mut series: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for character in table.components.keys() {
+        for component in classify_components(character, table, decompositions, readings) {
+            if component.role == ComponentRole::Phonetic {
+                series
+                    .entry(component.value)
+                    .or_insert_with(Vec::new)
+                    .push(character.to_owned());
+            }
+        }
+    }
+
+    series
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(phonetic, mut members)| {
+            members.sort();
+
+            PhoneticSeries {
+                readings: readings.get(&phonetic).cloned().unwrap_or_default(),
+                members: members
+                    .into_iter()
+                    .map(|character| SeriesMember {
+                        readings: readings.get(&character).cloned().unwrap_or_default(),
+                        character,
+                    })
+                    .collect(),
+                phonetic,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_group(character: &str, wade_giles_pinyins: &[&str]) -> Group {
+        Group {
+            simplified: character.to_string(),
+            details: vec![Detail {
+                id: character.to_string(),
+                pronunciation: wade_giles_pinyins
+                    .iter()
+                    .map(|pr| Pronunciation {
+                        pinyin: "".to_string(),
+                        wade_giles_pinyin: pr.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                simplified: character.to_string(),
+                traditional: character.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn get_data() -> (
+        ComponentTable,
+        HashMap<String, Decomposition>,
+        HashMap<String, Vec<String>>,
+    ) {
+        let groups = vec![
+            get_group("青", &["qing1"]),
+            get_group("清", &["qing1"]),
+            get_group("请", &["qing3"]),
+            get_group("晴", &["qing2"]),
+            get_group("精", &["jing1"]),
+            get_group("氵", &["shui3"]),
+            get_group("讠", &["yan2"]),
+            get_group("日", &["ri4"]),
+            get_group("米", &["mi3"]),
+            get_group("好", &["hao3", "hao4"]),
+            get_group("女", &["nu:3"]),
+            get_group("子", &["zi3"]),
+        ];
+
+        let mut table = ComponentTable::default();
+
+        for (character, components) in &[
+            ("清", vec!["氵", "青"]),
+            ("请", vec!["讠", "青"]),
+            ("晴", vec!["日", "青"]),
+            ("精", vec!["米", "青"]),
+            ("好", vec!["女", "子"]),
+        ] {
+            table.components.insert(
+                character.to_string(),
+                components.iter().map(|pr| Some(pr.to_string())).collect(),
+            );
+        }
+
+        let mut decompositions: HashMap<String, Decomposition> = HashMap::new();
+        decompositions.insert(
+            "好".to_string(),
+            Decomposition {
+                radical: vec![Radical {
+                    value: "女".to_string(),
+                    stroke_count: 3,
+                    meaning: "woman".to_string(),
+                    pinyin: "nǚ".to_string(),
+                }],
+                graphical: Vec::new(),
+                ids: None,
+            },
+        );
+
+        (table, decompositions, get_readings(&groups))
+    }
+
+    #[test]
+    fn should_get_final() {
+        assert_eq!(get_final("zhang"), "ang");
+        assert_eq!(get_final("ang"), "ang");
+        assert_eq!(get_final("r"), "r");
+    }
+
+    #[test]
+    fn should_classify_components() {
+        let (table, decompositions, readings) = get_data();
+
+        let result = classify_components("清", &table, &decompositions, &readings);
+        assert_eq!(
+            result,
+            vec![
+                ClassifiedComponent {
+                    value: "氵".to_string(),
+                    role: ComponentRole::Semantic
+                },
+                ClassifiedComponent {
+                    value: "青".to_string(),
+                    role: ComponentRole::Phonetic
+                }
+            ]
+        );
+
+        let roles: Vec<ComponentRole> =
+            classify_components("好", &table, &decompositions, &readings)
+                .into_iter()
+                .map(|pr| pr.role)
+                .collect();
+        assert_eq!(roles, vec![ComponentRole::Semantic, ComponentRole::Unknown]);
+    }
+
+    #[test]
+    fn should_group_phonetic_series() {
+        let (table, decompositions, readings) = get_data();
+        let result = get_phonetic_series(&table, &decompositions, &readings);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].phonetic, "青");
+        assert_eq!(result[0].readings, vec!["qing"]);
+
+        let members: Vec<&str> = result[0]
+            .members
+            .iter()
+            .map(|pr| pr.character.as_str())
+            .collect();
+        assert_eq!(members, vec!["晴", "清", "精", "请"]);
+        assert_eq!(result[0].members[2].readings, vec!["jing"]);
+    }
+}
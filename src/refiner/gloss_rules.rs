@@ -0,0 +1,261 @@
+use crate::models::*;
+use crate::refiner::refine_meaning_record::refine_meaning_record;
+use ::phf::{phf_map, Map};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// What a single gloss of a CEDICT-format line turned out to be.
+pub enum Gloss {
+    /// Points to the full forms of an abbreviation, listed in abbreviations.txt.
+    Abbreviation,
+    Variant(Variant),
+    /// Another reading; `pinyin` is left for the caller to fill in.
+    Pronunciation(Pronunciation),
+    Classifiers(Vec<Classifier>),
+    Meaning(Meaning),
+}
+
+/// Refinement rules for the glosses of one language. Dictionaries in a
+/// language without rules of its own keep their glosses as plain meanings.
+pub trait GlossRules {
+    /// ISO 639-1 code of the language.
+    fn get_language(&self) -> &str;
+
+    fn refine_gloss(&self, gloss: &str) -> Option<Gloss>;
+}
+
+pub fn get_gloss_rules(language: &str) -> Box<dyn GlossRules> {
+    match language {
+        "en" => Box::new(English),
+        "de" => Box::new(German),
+        _ => Box::new(Plain {
+            language: language.to_string(),
+        }),
+    }
+}
+
+fn get_plain_meaning(value: &str) -> Meaning {
+    Meaning {
+        reference_id: None,
+        context: None,
+        lexical_item: None,
+        value: Some(value.to_string()),
+        literal_meaning: None,
+        simplified: None,
+        traditional: None,
+        wade_giles_pinyin: None,
+        pinyin: None,
+        language: None,
+    }
+}
+
+/// CC-CEDICT conventions such as "abbr. for", "also written", "also pr.",
+/// "CL:" and "variant of".
+pub struct English;
+
+impl GlossRules for English {
+    fn get_language(&self) -> &str {
+        "en"
+    }
+
+    fn refine_gloss(&self, gloss: &str) -> Option<Gloss> {
+        lazy_static! {
+            static ref EXTRACT_CLASSIFIER_REGEX: Regex =
+                Regex::new(r"(.*?[^|])\|?(.*?)\[(.*?)\]").unwrap();
+            static ref EXTRACT_PINYIN_REGEX: Regex = Regex::new(r"\[(?P<pinyin>.*?)\]").unwrap();
+            static ref ALSO_WRITTEN_SIMPL_TRAD_PINYIN_REGEX: Regex =
+                Regex::new(r"^also written ([^|\[]+)(?:\|([^\[\]|]+))?(?:\[([^\[\]]+)])?").unwrap();
+            static ref COMPLEX_ABBR_REGEX: Regex = Regex::new(r"abbr\.\s(for|of|to)").unwrap();
+        }
+
+        if COMPLEX_ABBR_REGEX.is_match(gloss) {
+            return Some(Gloss::Abbreviation);
+        }
+
+        if gloss.starts_with("also written") {
+            let captures = ALSO_WRITTEN_SIMPL_TRAD_PINYIN_REGEX
+                .captures(gloss)
+                .unwrap();
+
+            return Some(Gloss::Variant(Variant {
+                reference_id: None,
+                simplified: captures.get(1).unwrap().as_str().to_owned(),
+                traditional: captures.get(2).map(|pr| pr.as_str().to_owned()),
+                wade_giles_pinyin: captures.get(3).map(|pr| pr.as_str().to_owned()),
+            }));
+        }
+
+        if gloss.contains("also pr.") {
+            let pronunciation = match EXTRACT_PINYIN_REGEX.captures(gloss) {
+                Some(captures) => Pronunciation {
+                    pinyin: "".to_string(),
+                    wade_giles_pinyin: captures.name("pinyin").unwrap().as_str().to_string(),
+                    other: None,
+                    jyutping: None,
+                },
+                None => Pronunciation {
+                    pinyin: "".to_string(),
+                    wade_giles_pinyin: "".to_string(),
+                    other: Some(str::replace(gloss, "also pr. ", "").trim().to_owned()),
+                    jyutping: None,
+                },
+            };
+
+            return Some(Gloss::Pronunciation(pronunciation));
+        }
+
+        if gloss.contains("CL:") {
+            let processed = str::replace(gloss, "CL:", "");
+            let mut classifiers = Vec::new();
+
+            for item in processed.trim().split(",") {
+                let captures = EXTRACT_CLASSIFIER_REGEX.captures(&item).unwrap();
+
+                classifiers.push(Classifier {
+                    reference_id: None,
+                    simplified: captures.get(1).unwrap().as_str().to_owned(),
+                    traditional: captures.get(2).unwrap().as_str().to_owned(),
+                    wade_giles_pinyin: captures.get(3).unwrap().as_str().to_owned(),
+                });
+            }
+
+            return Some(Gloss::Classifiers(classifiers));
+        }
+
+        refine_meaning_record(gloss).map(Gloss::Meaning)
+    }
+}
+
+static GERMAN_LEXICAL_ITEMS: Map<&'static str, &'static str> = phf_map! {
+    "(S)" => "noun",
+    "(V)" => "verb",
+    "(Adj)" => "adjective",
+    "(Adv)" => "adverb",
+    "(Num)" => "numeral",
+    "(Pron)" => "pronoun",
+    "(Präp)" => "preposition",
+    "(Konj)" => "conjunction",
+    "(Int)" => "interjection",
+    "(Zähl)" => "classifier",
+    "(Eig)" => "proper noun",
+    "(Sprichw)" => "proverb",
+};
+
+/// HanDeDict marks the part of speech in parentheses, "Tradition (S)", and
+/// entries nobody has reviewed yet with "(u.E.)".
+pub struct German;
+
+impl GlossRules for German {
+    fn get_language(&self) -> &str {
+        "de"
+    }
+
+    fn refine_gloss(&self, gloss: &str) -> Option<Gloss> {
+        let mut meaning = get_plain_meaning(gloss);
+        let mut value = gloss.to_string();
+
+        for (pattern, lexical_item) in GERMAN_LEXICAL_ITEMS.entries() {
+            if value.contains(pattern) {
+                value = str::replace(&value, pattern, "");
+                meaning.lexical_item = Some(lexical_item.to_string());
+            }
+        }
+
+        if value.contains("(u.E.)") {
+            value = str::replace(&value, "(u.E.)", "");
+            meaning.context = Some(vec!["unverified".to_string()]);
+        }
+
+        let value = value.trim();
+
+        if value.is_empty() {
+            return None;
+        }
+
+        meaning.value = Some(value.to_string());
+
+        Some(Gloss::Meaning(meaning))
+    }
+}
+
+pub struct Plain {
+    pub language: String,
+}
+
+impl GlossRules for Plain {
+    fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    fn refine_gloss(&self, gloss: &str) -> Option<Gloss> {
+        let value = gloss.trim();
+
+        if value.is_empty() {
+            return None;
+        }
+
+        Some(Gloss::Meaning(get_plain_meaning(value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_meaning(gloss: Option<Gloss>) -> Meaning {
+        match gloss {
+            Some(Gloss::Meaning(meaning)) => meaning,
+            _ => panic!("not a meaning"),
+        }
+    }
+
+    #[test]
+    fn should_refine_english_glosses() {
+        let rules = get_gloss_rules("en");
+
+        assert!(matches!(
+            rules.refine_gloss("abbr. for 北京大學|北京大学[Bei3 jing1 Da4 xue2]"),
+            Some(Gloss::Abbreviation)
+        ));
+
+        match rules.refine_gloss("CL:個|个[ge4],本[ben3]") {
+            Some(Gloss::Classifiers(classifiers)) => {
+                assert_eq!(classifiers.len(), 2);
+                assert_eq!(classifiers[1].simplified, "本");
+            }
+            _ => panic!("not classifiers"),
+        }
+
+        match rules.refine_gloss("also pr. [he4]") {
+            Some(Gloss::Pronunciation(pronunciation)) => {
+                assert_eq!(pronunciation.wade_giles_pinyin, "he4")
+            }
+            _ => panic!("not a pronunciation"),
+        }
+
+        let meaning = get_meaning(rules.refine_gloss("(coll.) don't mention it"));
+        assert_eq!(meaning.context.unwrap(), vec!["colloquial"]);
+    }
+
+    #[test]
+    fn should_refine_german_glosses() {
+        let rules = get_gloss_rules("de");
+        let meaning = get_meaning(rules.refine_gloss("Tradition (S) (u.E.)"));
+
+        assert_eq!(rules.get_language(), "de");
+        assert_eq!(meaning.value.unwrap(), "Tradition");
+        assert_eq!(meaning.lexical_item.unwrap(), "noun");
+        assert_eq!(meaning.context.unwrap(), vec!["unverified"]);
+        assert!(rules.refine_gloss("(S)").is_none());
+    }
+
+    #[test]
+    fn should_keep_glosses_of_other_languages() {
+        let rules = get_gloss_rules("fr");
+        let meaning = get_meaning(rules.refine_gloss("abbr. for tradition"));
+
+        assert_eq!(rules.get_language(), "fr");
+        assert_eq!(meaning.value.unwrap(), "abbr. for tradition");
+        assert_eq!(meaning.context, None);
+    }
+}
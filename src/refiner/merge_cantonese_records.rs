@@ -0,0 +1,111 @@
+use crate::models::CERecord;
+use crate::models::Cantonese;
+use crate::utils::get_detail_id::get_record_id;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Adds the CC-Canto entries that have no CC-CEDICT counterpart to `records`
+/// and returns their ids, along with the CC-Canto glosses of the entries that
+/// do have one, by id.
+///
+/// Groups are keyed by the simplified headword everywhere, in the exports as
+/// much as in lookups, so a Cantonese-only entry whose headword CC-CEDICT
+/// already has joins that group instead of getting one of its own. The
+/// caller tells it apart by its id.
+pub fn merge_cantonese_records(
+    records: &mut HashMap<String, Vec<CERecord>>,
+    cantonese: &Cantonese,
+) -> (HashSet<String>, HashMap<String, Vec<String>>) {
+    let ids: HashSet<String> = records.values().flatten().map(get_record_id).collect();
+    let mut added = HashSet::new();
+    let mut glosses: HashMap<String, Vec<String>> = HashMap::new();
+
+    for record in &cantonese.records {
+        let id = get_record_id(record);
+
+        if ids.contains(&id) {
+            let list = glosses.entry(id).or_insert_with(Vec::new);
+
+            for meaning in &record.meanings {
+                if !list.contains(meaning) {
+                    list.push(meaning.to_owned());
+                }
+            }
+
+            continue;
+        }
+
+        if !added.insert(id) {
+            continue;
+        }
+
+        records
+            .entry(record.simplified.to_owned())
+            .or_insert_with(Vec::new)
+            .push(record.clone());
+    }
+
+    (added, glosses)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::parse_ce_record::parse_ce_record;
+
+    #[test]
+    fn should_add_cantonese_only_records() {
+        let mut records = HashMap::new();
+        records.insert(
+            "是".to_string(),
+            vec![parse_ce_record("是 是 [shi4] /is/\n", 1)],
+        );
+        records.insert(
+            "系".to_string(),
+            vec![parse_ce_record("系 系 [xi4] /system/\n", 2)],
+        );
+
+        let cantonese = Cantonese {
+            readings: HashMap::new(),
+            records: vec![
+                parse_ce_record("是 是 [shi4] /to be/is/\n", 1),
+                parse_ce_record("嘅 嘅 [ge4] /(possessive particle)/\n", 2),
+                parse_ce_record("嘅 嘅 [ge4] /(possessive particle)/\n", 3),
+                parse_ce_record("係 系 [xi4] /to be/\n", 4),
+            ],
+        };
+
+        let (added, glosses) = merge_cantonese_records(&mut records, &cantonese);
+
+        assert_eq!(added.len(), 2);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records["是"].len(), 1);
+        assert_eq!(records["嘅"].len(), 1);
+        assert!(added.contains(&get_record_id(&records["嘅"][0])));
+        assert_eq!(
+            glosses[&get_record_id(&records["是"][0])],
+            vec!["to be", "is"]
+        );
+    }
+
+    #[test]
+    fn should_add_cantonese_only_records_to_existing_headword() {
+        let mut records = HashMap::new();
+        records.insert(
+            "系".to_string(),
+            vec![parse_ce_record("系 系 [xi4] /system/\n", 1)],
+        );
+
+        let cantonese = Cantonese {
+            readings: HashMap::new(),
+            records: vec![parse_ce_record("係 系 [xi4] /to be/\n", 1)],
+        };
+
+        let (added, _) = merge_cantonese_records(&mut records, &cantonese);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records["系"].len(), 2);
+        assert_eq!(records["系"][1].traditional, "係");
+        assert!(added.contains(&get_record_id(&records["系"][1])));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::models::Detail;
+
+/// Appends the items of `other` that `existing` doesn't have yet.
+fn merge_list<T>(
+    existing: &mut Option<Vec<T>>,
+    other: Option<Vec<T>>,
+    is_same: fn(&T, &T) -> bool,
+) {
+    let other = match other {
+        Some(other) => other,
+        None => return,
+    };
+    let list = existing.get_or_insert_with(Vec::new);
+
+    for item in other {
+        if !list.iter().any(|pr| is_same(pr, &item)) {
+            list.push(item);
+        }
+    }
+}
+
+/// Folds details with the same id, repeated CEDICT lines or the same headword
+/// and reading from dictionaries in different languages, into the first one.
+pub fn merge_details(details: Vec<Detail>) -> Vec<Detail> {
+    let mut list: Vec<Detail> = Vec::with_capacity(details.len());
+
+    for detail in details {
+        let existing = match list.iter_mut().find(|pr| pr.id == detail.id) {
+            Some(existing) => existing,
+            None => {
+                list.push(detail);
+                continue;
+            }
+        };
+
+        existing.meanings.extend(detail.meanings);
+
+        for pronunciation in detail.pronunciation {
+            let same = existing.pronunciation.iter_mut().find(|pr| {
+                pr.wade_giles_pinyin == pronunciation.wade_giles_pinyin
+                    && pr.other == pronunciation.other
+            });
+
+            match same {
+                Some(same) => merge_list(&mut same.jyutping, pronunciation.jyutping, |a, b| a == b),
+                None => existing.pronunciation.push(pronunciation),
+            }
+        }
+
+        if existing.variant.is_none() {
+            existing.variant = detail.variant;
+        }
+
+        if existing.decomposition.is_none() {
+            existing.decomposition = detail.decomposition;
+        }
+
+        if existing.strokes.is_none() {
+            existing.strokes = detail.strokes;
+        }
+
+        merge_list(&mut existing.classifiers, detail.classifiers, |a, b| {
+            a.simplified == b.simplified
+                && a.traditional == b.traditional
+                && a.wade_giles_pinyin == b.wade_giles_pinyin
+        });
+        merge_list(&mut existing.similar, detail.similar, |a, b| a == b);
+        merge_list(&mut existing.examples, detail.examples, |a, b| a.id == b.id);
+        merge_list(&mut existing.tags, detail.tags, |a, b| a == b);
+    }
+
+    list
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::*;
+
+    fn get_detail(id: &str, value: &str, language: &str) -> Detail {
+        Detail {
+            id: id.to_string(),
+            pronunciation: vec![Pronunciation {
+                pinyin: "chuán tǒng".to_string(),
+                wade_giles_pinyin: "chuan2 tong3".to_string(),
+                ..Default::default()
+            }],
+            simplified: "传统".to_string(),
+            traditional: "傳統".to_string(),
+            meanings: vec![Meaning {
+                value: Some(value.to_string()),
+                language: Some(language.to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_merge_meanings_of_same_detail() {
+        let details = merge_details(vec![
+            get_detail("a", "tradition", "en"),
+            get_detail("b", "tradition", "en"),
+            get_detail("a", "Tradition", "de"),
+        ]);
+
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].meanings.len(), 2);
+        assert_eq!(
+            details[0].meanings[1].language.as_ref().unwrap().as_str(),
+            "de"
+        );
+        assert_eq!(details[1].meanings.len(), 1);
+    }
+
+    #[test]
+    fn should_merge_readings_and_classifiers_of_same_detail() {
+        let mut first = get_detail("a", "tradition", "en");
+        first.classifiers = Some(vec![Classifier {
+            reference_id: None,
+            simplified: "个".to_string(),
+            traditional: "個".to_string(),
+            wade_giles_pinyin: "ge4".to_string(),
+        }]);
+        let mut second = get_detail("a", "convention", "en");
+        second.pronunciation.push(Pronunciation {
+            wade_giles_pinyin: "chuan2 tong4".to_string(),
+            ..Default::default()
+        });
+        second.classifiers = Some(vec![
+            first.classifiers.as_ref().unwrap()[0].clone(),
+            Classifier {
+                reference_id: None,
+                simplified: "种".to_string(),
+                traditional: "種".to_string(),
+                wade_giles_pinyin: "zhong3".to_string(),
+            },
+        ]);
+
+        let details = merge_details(vec![first, second]);
+        let pinyins: Vec<&str> = details[0]
+            .pronunciation
+            .iter()
+            .map(|pr| pr.wade_giles_pinyin.as_str())
+            .collect();
+
+        assert_eq!(details.len(), 1);
+        assert_eq!(pinyins, vec!["chuan2 tong3", "chuan2 tong4"]);
+        assert_eq!(details[0].classifiers.as_ref().unwrap().len(), 2);
+        assert_eq!(details[0].meanings.len(), 2);
+    }
+}
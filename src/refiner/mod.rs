@@ -1,9 +1,16 @@
+pub mod build_decomposition_tree;
+pub mod classify_components;
+pub mod gloss_rules;
+pub mod merge_cantonese_records;
+pub mod merge_details;
+pub mod parse_ids;
 pub mod refine_meaning_record;
 pub mod refine_records;
+pub mod resolve_references;
 pub mod to_pinyin;
 use crate::models::*;
 use crate::utils::*;
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::error::Error;
 use std::path::Path;
 
@@ -12,3 +19,33 @@ pub fn get_cached_refined_records(cache_dict_path: &Path) -> Result<Vec<Group>,
     let dict = serde_json::from_slice(bytes)?;
     Ok(dict)
 }
+
+pub fn try_get_refined_records(
+    cedict_ts_path: &Path,
+    cache_list_path: &Path,
+    cache_dict_path: &Path,
+    cache_refined_path: &Path,
+    current_directory: &Path,
+    public_directory: &Path,
+    assets_directory: &Path,
+) -> Result<Vec<Group>, Box<dyn Error>> {
+    if cache_refined_path.exists() {
+        match get_cached_refined_records(cache_refined_path) {
+            Ok(refined_records) => return Ok(refined_records),
+            Err(error) => warn!(
+                "Could not read {:?}, refining again: {}",
+                cache_refined_path, error
+            ),
+        }
+    }
+
+    let list = try_get_ce_dict_records(cedict_ts_path, cache_list_path)?;
+    let grouped_records = get_group_ce_records_by_simplified(&list, cache_dict_path)?;
+
+    refine_records::refine_records(
+        grouped_records,
+        current_directory,
+        public_directory,
+        assets_directory,
+    )
+}
@@ -0,0 +1,146 @@
+use crate::models::IdsNode;
+use crate::models::IdsOperator;
+use std::error::Error;
+
+fn get_operator(character: char) -> Option<(IdsOperator, usize)> {
+    match character {
+        '⿰' => Some((IdsOperator::LeftToRight, 2)),
+        '⿱' => Some((IdsOperator::AboveToBelow, 2)),
+        '⿲' => Some((IdsOperator::LeftToMiddleAndRight, 3)),
+        '⿳' => Some((IdsOperator::AboveToMiddleAndBelow, 3)),
+        '⿴' => Some((IdsOperator::FullSurround, 2)),
+        '⿵' => Some((IdsOperator::SurroundFromAbove, 2)),
+        '⿶' => Some((IdsOperator::SurroundFromBelow, 2)),
+        '⿷' => Some((IdsOperator::SurroundFromLeft, 2)),
+        '⿸' => Some((IdsOperator::SurroundFromUpperLeft, 2)),
+        '⿹' => Some((IdsOperator::SurroundFromUpperRight, 2)),
+        '⿺' => Some((IdsOperator::SurroundFromLowerLeft, 2)),
+        '⿻' => Some((IdsOperator::Overlaid, 2)),
+        _ => None,
+    }
+}
+
+/// Parses an ideographic description sequence such as "⿰女子". Components
+/// without a code point, written "{12}" or "&CDP-8B7C;" in IDS data files, are
+/// kept as single components.
+pub fn parse_ids(value: &str) -> Result<IdsNode, Box<dyn Error>> {
+    let characters: Vec<char> = value.trim().chars().collect();
+    let mut position = 0;
+    let node = parse_node(&characters, &mut position)?;
+
+    if position != characters.len() {
+        return Err(format!("unexpected trailing characters in {}", value).into());
+    }
+
+    Ok(node)
+}
+
+fn parse_node(characters: &[char], position: &mut usize) -> Result<IdsNode, Box<dyn Error>> {
+    let character = *characters
+        .get(*position)
+        .ok_or("unexpected end of ideographic description sequence")?;
+    *position = *position + 1;
+
+    if let Some((operator, count)) = get_operator(character) {
+        let mut components = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            components.push(parse_node(characters, position)?);
+        }
+
+        return Ok(IdsNode::Composition {
+            operator,
+            components,
+        });
+    }
+
+    let closing = match character {
+        '{' => '}',
+        '&' => ';',
+        _ => return Ok(IdsNode::Component(character.to_string())),
+    };
+
+    let start = *position - 1;
+
+    while *position < characters.len() && characters[*position] != closing {
+        *position = *position + 1;
+    }
+
+    if *position == characters.len() {
+        return Err("unterminated component reference".into());
+    }
+
+    *position = *position + 1;
+
+    Ok(IdsNode::Component(
+        characters[start..*position].iter().collect(),
+    ))
+}
+
+impl IdsNode {
+    pub fn get_components(&self) -> Vec<&str> {
+        match self {
+            IdsNode::Component(value) => vec![value.as_str()],
+            IdsNode::Composition { components, .. } => components
+                .iter()
+                .flat_map(|pr| pr.get_components())
+                .collect(),
+        }
+    }
+
+    pub fn get_operator(&self) -> Option<IdsOperator> {
+        match self {
+            IdsNode::Component(_) => None,
+            IdsNode::Composition { operator, .. } => Some(*operator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_flat_sequence() {
+        let result = parse_ids("⿰女子").unwrap();
+        assert_eq!(
+            result,
+            IdsNode::Composition {
+                operator: IdsOperator::LeftToRight,
+                components: vec![
+                    IdsNode::Component("女".to_string()),
+                    IdsNode::Component("子".to_string())
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn should_parse_nested_sequence() {
+        let result = parse_ids("⿱⿰耳又女").unwrap();
+        assert_eq!(result.get_operator(), Some(IdsOperator::AboveToBelow));
+        assert_eq!(result.get_components(), vec!["耳", "又", "女"]);
+
+        let result = parse_ids("⿳⿲丨&CDP-8B7C;丨{12}一").unwrap();
+        assert_eq!(
+            result.get_components(),
+            vec!["丨", "&CDP-8B7C;", "丨", "{12}", "一"]
+        );
+        assert_eq!(parse_ids("女").unwrap().get_operator(), None);
+    }
+
+    #[test]
+    fn should_serialize_as_nested_json() {
+        let value = serde_json::to_value(parse_ids("⿴囗玉").unwrap()).unwrap();
+        assert_eq!(value["operator"], "full-surround");
+        assert_eq!(value["components"][1], "玉");
+    }
+
+    #[test]
+    fn should_reject_invalid_sequences() {
+        assert!(parse_ids("⿰女").is_err());
+        assert!(parse_ids("⿰女子子").is_err());
+        assert!(parse_ids("{12").is_err());
+        assert!(parse_ids("").is_err());
+    }
+}
@@ -79,6 +79,7 @@ pub fn refine_meaning_record(meaning: &str) -> Option<Meaning> {
     }
 
     let mut meaning_record = Meaning {
+        reference_id: None,
         context: None,
         lexical_item: None,
         simplified: None,
@@ -87,6 +88,7 @@ pub fn refine_meaning_record(meaning: &str) -> Option<Meaning> {
         pinyin: None,
         literal_meaning: None,
         value: Some(meaning.to_string()),
+        language: None,
     };
 
     let mut meaning_record_context: Option<Vec<String>> = None;
@@ -1,20 +1,35 @@
 use crate::models::*;
-use crate::refiner::refine_meaning_record::refine_meaning_record;
+use crate::refiner::gloss_rules::get_gloss_rules;
+use crate::refiner::gloss_rules::Gloss;
+use crate::refiner::merge_cantonese_records::merge_cantonese_records;
+use crate::refiner::merge_details::merge_details;
+use crate::refiner::resolve_references::resolve_references;
 use crate::refiner::to_pinyin::to_pinyin;
+use crate::search::similar_characters::SimilarityIndex;
+use crate::text::examples::get_examples_by_id;
+use crate::text::segmenter::Segmenter;
 use crate::utils::get_abbreviations_from_file::get_abbreviations_from_file;
+use crate::utils::get_cantonese_from_directory::get_cantonese_from_directory;
 use crate::utils::get_decomposition_from_file::get_decomposition_from_file;
 use crate::utils::get_descriptors_from_file::get_descriptors_from_file;
-use crate::utils::get_radicals_from_file::get_radicals_from_file;
+use crate::utils::get_detail_id::get_record_id;
+use crate::utils::get_dictionaries_from_directory::get_dictionaries_from_directory;
+use crate::utils::get_hsk_levels_from_directory::get_hsk_levels_from_directory;
+use crate::utils::get_radicals_from_file::get_radicals;
+use crate::utils::get_stroke_graphics_from_file::get_stroke_graphics_from_file;
+use crate::utils::get_tatoeba_from_directory::get_tatoeba_from_directory;
 use crate::utils::*;
 use crate::CERecord;
-use lazy_static::lazy_static;
-use regex::Regex;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::error::Error;
 use std::path::Path;
 
+static SIMILAR_LIMIT: usize = 5;
+static EXAMPLE_LIMIT: usize = 3;
+
 pub fn refine_records(
-    records: HashMap<String, Vec<CERecord>>,
+    mut records: HashMap<String, Vec<CERecord>>,
     current_directory: &Path,
     public_directory: &Path,
     assets_directory: &Path,
@@ -23,26 +38,64 @@ pub fn refine_records(
     let pinyin_path = temp.to_str().unwrap();
     let pinyins_map = get_pinyins_map(pinyin_path)?;
 
-    let radicals = get_radicals_from_file(&assets_directory.join("radicals.txt"))?;
+    let radicals = get_radicals(assets_directory)?;
     let decomposition =
         get_decomposition_from_file(&assets_directory.join("abbreviations.txt"), &radicals);
     let abbreviations = get_abbreviations_from_file(&assets_directory.join("abbreviations.txt"))?;
     let descriptors = get_descriptors_from_file(&assets_directory.join("descriptor.txt"))?;
     let stroke_order_map = get_stroke_order_map(&assets_directory.join("stroke-order.txt"))?;
+    let similarity_index = SimilarityIndex::new(
+        &get_decomposition_from_file(
+            &assets_directory.join("character-decomposition.txt"),
+            &radicals,
+        )?,
+        stroke_order_map.clone(),
+    );
+
+    let graphics_path = assets_directory.join("graphics.txt");
+    let stroke_graphics = if graphics_path.exists() {
+        get_stroke_graphics_from_file(&graphics_path)?
+    } else {
+        HashMap::new()
+    };
+
+    let cantonese = get_cantonese_from_directory(assets_directory)?;
+    let (cantonese_only, mut cantonese_glosses) = merge_cantonese_records(&mut records, &cantonese);
+
+    for record in get_dictionaries_from_directory(&assets_directory.join("dictionaries"))? {
+        records
+            .entry(record.simplified.to_owned())
+            .or_insert_with(Vec::new)
+            .push(record);
+    }
+
+    let tatoeba_directory = assets_directory.join("tatoeba");
+    let examples = if tatoeba_directory.exists() {
+        let list: Vec<CERecord> = records.values().flatten().cloned().collect();
+        let hsk_levels = get_hsk_levels_from_directory(assets_directory)?;
+        let known_ids: HashSet<String> = list
+            .iter()
+            .filter(|pr| {
+                hsk_levels
+                    .iter()
+                    .any(|level| level.words.contains(&pr.simplified))
+            })
+            .map(get_record_id)
+            .collect();
+
+        get_examples_by_id(
+            &get_tatoeba_from_directory(&tatoeba_directory)?,
+            &Segmenter::new(&list),
+            &known_ids,
+            EXAMPLE_LIMIT,
+        )
+    } else {
+        HashMap::new()
+    };
 
     let mut index = 1;
     let mut grouped_records: Vec<Group> = Vec::with_capacity(116725);
 
-    lazy_static! {
-        static ref EXTRACT_CLASSIFIER_REGEX: Regex =
-            Regex::new(r"(.*?[^|])\|?(.*?)\[(.*?)\]").unwrap();
-        static ref EXTRACT_PINYIN_REGEX: Regex = Regex::new(r"\[(?P<pinyin>.*?)\]").unwrap();
-        static ref EXTRACT_IDIOM_REGEX: Regex = Regex::new(r"\(idiom,?[^\)].*\)").unwrap();
-        static ref ALSO_WRITTEN_SIMPL_TRAD_PINYIN_REGEX: Regex =
-            Regex::new(r"^also written ([^|\[]+)(?:\|([^\[\]|]+))?(?:\[([^\[\]]+)])?").unwrap();
-        static ref COMPLEX_ABBR_REGEX: Regex = Regex::new(r"abbr\.\s(for|of|to)").unwrap();
-    }
-
     for (key, records) in records {
         info!("{}: Processing: {}", index, key);
         index = index + 1;
@@ -57,6 +110,7 @@ pub fn refine_records(
             let pinyin = to_pinyin(&record.wade_giles_pinyin, &pinyins_map);
 
             let mut detail = Detail {
+                id: get_record_id(&record),
                 meanings: Vec::new(),
                 pronunciation: Vec::new(),
                 simplified: record.simplified.clone(),
@@ -67,6 +121,9 @@ pub fn refine_records(
                 tags: None,
                 classifiers: None,
                 decomposition: None,
+                similar: similarity_index.get_similar_headwords(&record.simplified, SIMILAR_LIMIT),
+                strokes: stroke_graphics.get(&record.simplified).cloned(),
+                examples: examples.get(&get_record_id(&record)).cloned(),
                 variant: None,
                 traditional: record.traditional,
             };
@@ -75,124 +132,96 @@ pub fn refine_records(
                 pinyin: pinyin,
                 wade_giles_pinyin: record.wade_giles_pinyin,
                 other: None,
+                jyutping: cantonese.readings.get(&detail.id).cloned(),
             });
 
+            if cantonese_only.contains(&detail.id) {
+                detail.tags = Some(vec!["cantonese".to_string()]);
+            }
+
+            let rules = get_gloss_rules(&record.language);
+            let language = Some(rules.get_language().to_string());
             let mut meanings = detail.meanings;
 
             for meaning in record.meanings {
                 let key = record.simplified.clone() + &meaning;
 
-                if COMPLEX_ABBR_REGEX.is_match(&meaning) {
-                    if let Some(list) = abbreviations.get(&record.simplified) {
-                        for item in list {
-                            meanings.push(Meaning {
-                                context: Some(vec!["abbreviation".to_string()]),
-                                lexical_item: None,
-                                simplified: item.simplified.to_owned(),
-                                traditional: item.traditional.to_owned(),
-                                literal_meaning: None,
-                                pinyin: None,
-                                value: item.value.to_owned(),
-                                wade_giles_pinyin: item.wade_giles_pinyin.to_owned(),
-                            });
+                match rules.refine_gloss(&meaning) {
+                    Some(Gloss::Abbreviation) => {
+                        if let Some(list) = abbreviations.get(&record.simplified) {
+                            for item in list {
+                                meanings.push(Meaning {
+                                    reference_id: None,
+                                    context: Some(vec!["abbreviation".to_string()]),
+                                    lexical_item: None,
+                                    simplified: item.simplified.to_owned(),
+                                    traditional: item.traditional.to_owned(),
+                                    literal_meaning: None,
+                                    pinyin: None,
+                                    value: item.value.to_owned(),
+                                    wade_giles_pinyin: item.wade_giles_pinyin.to_owned(),
+                                    language: language.clone(),
+                                });
+                            }
                         }
                     }
-                    continue;
-                }
-
-                if meaning.starts_with("also written") {
-                    let captures = ALSO_WRITTEN_SIMPL_TRAD_PINYIN_REGEX
-                        .captures(&meaning)
-                        .unwrap();
-                    let simplified = captures.get(1).unwrap().as_str().to_owned();
-                    let traditional = captures.get(2).map(|pr| pr.as_str().to_owned());
-                    let wade_giles_pinyin = captures.get(3).map(|pr| pr.as_str().to_owned());
-
-                    let variant = Variant {
-                        simplified,
-                        traditional,
-                        wade_giles_pinyin,
-                    };
-
-                    detail.variant = Some(variant);
-                    continue;
-                }
-
-                if meaning.contains("also pr.") {
-                    let captures = EXTRACT_PINYIN_REGEX.captures(&meaning);
-
-                    if captures.is_none() {
-                        let mut processed = str::replace(&meaning, "also pr. ", "");
-                        processed = processed.trim().to_owned();
-
-                        detail.pronunciation.push(Pronunciation {
-                            pinyin: "".to_string(),
-                            wade_giles_pinyin: "".to_string(),
-                            other: Some(processed),
-                        });
-                        continue;
+                    Some(Gloss::Variant(variant)) => {
+                        detail.variant = Some(variant);
                     }
+                    Some(Gloss::Pronunciation(mut pronunciation)) => {
+                        if pronunciation.other.is_none() {
+                            pronunciation.pinyin =
+                                to_pinyin(&pronunciation.wade_giles_pinyin, &pinyins_map);
+                        }
 
-                    let wade_giles_pinyin = captures
-                        .unwrap()
-                        .name("pinyin")
-                        .unwrap()
-                        .as_str()
-                        .to_string();
-                    let pinyin = to_pinyin(&wade_giles_pinyin, &pinyins_map);
-
-                    detail.pronunciation.push(Pronunciation {
-                        pinyin: pinyin,
-                        wade_giles_pinyin: wade_giles_pinyin,
-                        other: None,
-                    });
-                    continue;
-                }
-
-                if meaning.contains("CL:") {
-                    let mut processed = str::replace(&meaning, "CL:", "");
-                    processed = processed.trim().to_owned();
-
-                    for item in processed.split(",") {
-                        let captures = EXTRACT_CLASSIFIER_REGEX.captures(&item).unwrap();
-
-                        let classifier = Classifier {
-                            simplified: captures.get(1).unwrap().as_str().to_owned(),
-                            traditional: captures.get(2).unwrap().as_str().to_owned(),
-                            wade_giles_pinyin: captures.get(3).unwrap().as_str().to_owned(),
-                        };
-
+                        detail.pronunciation.push(pronunciation);
+                    }
+                    Some(Gloss::Classifiers(list)) => {
                         let mut classifiers = detail.classifiers.clone().unwrap_or_default();
-                        classifiers.push(classifier);
+                        classifiers.extend(list);
                         detail.classifiers = Some(classifiers);
                     }
-                    continue;
-                }
+                    Some(Gloss::Meaning(mut record)) => {
+                        let descriptor = descriptors.get(&key);
 
-                let meaning_record = refine_meaning_record(&meaning);
+                        if descriptor.is_some() {
+                            let descriptor = descriptor.unwrap();
+                            let tags = descriptor.tags.clone().unwrap_or_default();
+                            record.lexical_item = descriptor.lexical_item.clone();
+                            let mut detail_tags = detail.tags.clone().unwrap_or_default();
 
-                if let Some(mut record) = meaning_record {
-                    let descriptor = descriptors.get(&key);
+                            for tag in tags {
+                                if detail_tags.contains(&tag.to_string()) {
+                                    continue;
+                                }
 
-                    if descriptor.is_some() {
-                        let descriptor = descriptor.unwrap();
-                        let tags = descriptor.tags.clone().unwrap_or_default();
-                        record.lexical_item = descriptor.lexical_item.clone();
-                        let mut detail_tags = detail.tags.clone().unwrap_or_default();
-
-                        for tag in tags {
-                            if detail_tags.contains(&tag.to_string()) {
-                                continue;
+                                detail_tags.push(tag.to_string());
                             }
 
-                            detail_tags.push(tag.to_string());
+                            detail.tags = Some(detail_tags);
                         }
 
-                        detail.tags = Some(detail_tags);
+                        record.language = language.clone();
+                        meanings.push(record);
                     }
+                    None => {}
+                }
+            }
 
+            // CC-Canto senses of a headword CC-CEDICT has, added to one detail
+            for gloss in cantonese_glosses.remove(&detail.id).unwrap_or_default() {
+                if let Some(Gloss::Meaning(mut record)) =
+                    get_gloss_rules(DEFAULT_LANGUAGE).refine_gloss(&gloss)
+                {
+                    if meanings.iter().any(|pr| pr.value == record.value) {
+                        continue;
+                    }
+
+                    let mut context = record.context.unwrap_or_default();
+                    context.push("cantonese".to_string());
+                    record.context = Some(context);
+                    record.language = Some(DEFAULT_LANGUAGE.to_string());
                     meanings.push(record);
-                    continue;
                 }
             }
 
@@ -201,9 +230,12 @@ pub fn refine_records(
             new_record.details.push(detail);
         }
 
+        new_record.details = merge_details(new_record.details);
         grouped_records.push(new_record);
     }
 
+    resolve_references(&mut grouped_records);
+
     Ok(grouped_records)
 }
 
@@ -228,6 +260,7 @@ mod test {
             simplified: key.to_string(),
             traditional: "交通大學".to_string(),
             wade_giles_pinyin: "jiao1 tong1 da4 xue2".to_string(),
+            language: DEFAULT_LANGUAGE.to_string(),
         };
         group.push(expected.clone());
         records.insert(key.to_owned(), group);
@@ -0,0 +1,184 @@
+use crate::models::*;
+use std::collections::HashMap;
+
+fn normalize_pinyin(wade_giles_pinyin: &str) -> String {
+    wade_giles_pinyin
+        .to_lowercase()
+        .replace("u:", "v")
+        .split_whitespace()
+        .collect()
+}
+
+fn find_reference_id(
+    ids: &HashMap<String, Vec<(String, String)>>,
+    simplified: &str,
+    traditional: Option<&str>,
+    wade_giles_pinyin: Option<&str>,
+) -> Option<String> {
+    let pinyin = wade_giles_pinyin.map(normalize_pinyin);
+    let headwords: Vec<&str> = Some(simplified).into_iter().chain(traditional).collect();
+
+    if let Some(pinyin) = &pinyin {
+        for headword in &headwords {
+            let candidates = ids.get(*headword).map(|pr| pr.as_slice()).unwrap_or(&[]);
+
+            if let Some((_, id)) = candidates.iter().find(|(candidate, _)| candidate == pinyin) {
+                return Some(id.to_owned());
+            }
+        }
+    }
+
+    headwords
+        .iter()
+        .filter_map(|pr| ids.get(*pr))
+        .filter_map(|pr| pr.first())
+        .map(|(_, id)| id.to_owned())
+        .next()
+}
+
+pub fn resolve_references(groups: &mut [Group]) {
+    let mut ids: HashMap<String, Vec<(String, String)>> = HashMap::with_capacity(groups.len() * 2);
+
+    for group in groups.iter() {
+        for detail in &group.details {
+            let pinyin = detail
+                .pronunciation
+                .first()
+                .map(|pr| normalize_pinyin(&pr.wade_giles_pinyin))
+                .unwrap_or_default();
+
+            ids.entry(detail.simplified.to_owned())
+                .or_insert_with(Vec::new)
+                .push((pinyin.to_owned(), detail.id.to_owned()));
+
+            if detail.traditional != detail.simplified {
+                ids.entry(detail.traditional.to_owned())
+                    .or_insert_with(Vec::new)
+                    .push((pinyin, detail.id.to_owned()));
+            }
+        }
+    }
+
+    for group in groups.iter_mut() {
+        for detail in group.details.iter_mut() {
+            for meaning in detail.meanings.iter_mut() {
+                if let Some(simplified) = &meaning.simplified {
+                    meaning.reference_id = find_reference_id(
+                        &ids,
+                        simplified,
+                        meaning.traditional.as_deref(),
+                        meaning.wade_giles_pinyin.as_deref(),
+                    );
+                }
+            }
+
+            if let Some(variant) = detail.variant.as_mut() {
+                variant.reference_id = find_reference_id(
+                    &ids,
+                    &variant.simplified,
+                    variant.traditional.as_deref(),
+                    variant.wade_giles_pinyin.as_deref(),
+                );
+            }
+
+            for classifier in detail.classifiers.iter_mut().flatten() {
+                classifier.reference_id = find_reference_id(
+                    &ids,
+                    &classifier.simplified,
+                    Some(&classifier.traditional),
+                    Some(&classifier.wade_giles_pinyin),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_detail(id: &str, simplified: &str, traditional: &str, pinyin: &str) -> Detail {
+        Detail {
+            id: id.to_string(),
+            pronunciation: vec![Pronunciation {
+                pinyin: "".to_string(),
+                wade_giles_pinyin: pinyin.to_string(),
+                ..Default::default()
+            }],
+            simplified: simplified.to_string(),
+            traditional: traditional.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_resolve_references() {
+        let mut detail = get_detail("1", "书", "書", "shu1");
+        detail.classifiers = Some(vec![Classifier {
+            reference_id: None,
+            simplified: "本".to_string(),
+            traditional: "本".to_string(),
+            wade_giles_pinyin: "ben3".to_string(),
+        }]);
+        detail.meanings.push(Meaning {
+            context: Some(vec!["variant".to_string()]),
+            simplified: Some("行".to_string()),
+            traditional: Some("行".to_string()),
+            wade_giles_pinyin: Some("Hang2".to_string()),
+            ..Default::default()
+        });
+
+        let mut groups = vec![
+            Group {
+                simplified: "书".to_string(),
+                details: vec![detail],
+                ..Default::default()
+            },
+            Group {
+                simplified: "本".to_string(),
+                details: vec![get_detail("2", "本", "本", "ben3")],
+                ..Default::default()
+            },
+            Group {
+                simplified: "行".to_string(),
+                details: vec![
+                    get_detail("3", "行", "行", "xing2"),
+                    get_detail("4", "行", "行", "hang2"),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        resolve_references(&mut groups);
+
+        let detail = &groups[0].details[0];
+        assert_eq!(
+            detail.classifiers.as_ref().unwrap()[0].reference_id,
+            Some("2".to_string())
+        );
+        assert_eq!(detail.meanings[0].reference_id, Some("4".to_string()));
+    }
+
+    #[test]
+    fn should_leave_unknown_references_empty() {
+        let mut detail = get_detail("1", "书", "書", "shu1");
+        detail.variant = Some(Variant {
+            reference_id: None,
+            simplified: "冊".to_string(),
+            traditional: None,
+            wade_giles_pinyin: None,
+        });
+        let mut groups = vec![Group {
+            simplified: "书".to_string(),
+            details: vec![detail],
+            ..Default::default()
+        }];
+
+        resolve_references(&mut groups);
+
+        assert_eq!(
+            groups[0].details[0].variant.as_ref().unwrap().reference_id,
+            None
+        );
+    }
+}
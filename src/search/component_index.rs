@@ -0,0 +1,281 @@
+use crate::models::Decomposition;
+use crate::models::IdsOperator;
+use crate::refiner::parse_ids::parse_ids;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentMatch {
+    pub character: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_stroke_count: Option<u8>,
+}
+
+pub struct ComponentIndex {
+    characters: HashMap<String, BTreeSet<String>>,
+    structures: HashMap<String, IdsOperator>,
+    stroke_counts: HashMap<String, u8>,
+}
+
+/// Splits "女耳" or "女, 耳" into its components.
+pub fn parse_components(query: &str) -> Vec<String> {
+    let mut components: Vec<String> = Vec::new();
+
+    for character in query.chars() {
+        if character.is_whitespace() || character == ',' || character == '，' {
+            continue;
+        }
+
+        let component = character.to_string();
+
+        if !components.contains(&component) {
+            components.push(component);
+        }
+    }
+
+    components
+}
+
+impl ComponentIndex {
+    pub fn new(
+        decompositions: &HashMap<String, Decomposition>,
+        stroke_counts: HashMap<String, u8>,
+    ) -> Self {
+        let mut characters: HashMap<String, BTreeSet<String>> = HashMap::new();
+        let mut structures: HashMap<String, IdsOperator> = HashMap::new();
+        let mut stroke_counts = stroke_counts;
+
+        for (character, decomposition) in decompositions {
+            for radical in &decomposition.radical {
+                characters
+                    .entry(radical.value.to_owned())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(character.to_owned());
+
+                stroke_counts
+                    .entry(radical.value.to_owned())
+                    .or_insert(radical.stroke_count);
+            }
+
+            for component in &decomposition.graphical {
+                if component.is_empty() || component == character {
+                    continue;
+                }
+
+                characters
+                    .entry(component.to_owned())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(character.to_owned());
+            }
+
+            let node = match decomposition.ids.as_ref().map(|pr| parse_ids(pr)) {
+                Some(Ok(node)) => node,
+                _ => continue,
+            };
+
+            if let Some(operator) = node.get_operator() {
+                structures.insert(character.to_owned(), operator);
+            }
+
+            for component in node.get_components() {
+                if component == character {
+                    continue;
+                }
+
+                characters
+                    .entry(component.to_string())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(character.to_owned());
+            }
+        }
+
+        Self {
+            characters,
+            structures,
+            stroke_counts,
+        }
+    }
+
+    fn get_remaining_stroke_count(&self, character: &str, components: &[String]) -> Option<u8> {
+        let total = *self.stroke_counts.get(character)?;
+        let mut used: u8 = 0;
+
+        for component in components {
+            used = used.checked_add(*self.stroke_counts.get(component)?)?;
+        }
+
+        total.checked_sub(used)
+    }
+
+    /// Characters containing every component, ordered by stroke count. When
+    /// `remaining_stroke_count` is given, only characters whose strokes outside
+    /// the components add up to it are kept, as with the radical lookup of a
+    /// paper dictionary. `structure` keeps characters whose IDS starts with the
+    /// given operator, such as left-to-right for 好.
+    pub fn search(
+        &self,
+        components: &[String],
+        remaining_stroke_count: Option<u8>,
+        structure: Option<IdsOperator>,
+    ) -> Vec<ComponentMatch> {
+        let mut sets = components.iter().map(|pr| self.characters.get(pr));
+
+        let mut result: BTreeSet<&String> = match sets.next() {
+            Some(Some(first)) => first.iter().collect(),
+            _ => return Vec::new(),
+        };
+
+        for set in sets {
+            let set = match set {
+                Some(set) => set,
+                None => return Vec::new(),
+            };
+
+            result.retain(|pr| set.contains(*pr));
+        }
+
+        let mut matches: Vec<ComponentMatch> = result
+            .into_iter()
+            .map(|character| ComponentMatch {
+                character: character.to_owned(),
+                stroke_count: self.stroke_counts.get(character).cloned(),
+                remaining_stroke_count: self.get_remaining_stroke_count(character, components),
+            })
+            .filter(|pr| {
+                remaining_stroke_count.is_none()
+                    || pr.remaining_stroke_count == remaining_stroke_count
+            })
+            .filter(|pr| {
+                structure.is_none() || self.structures.get(&pr.character) == structure.as_ref()
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            a.stroke_count
+                .unwrap_or(u8::MAX)
+                .cmp(&b.stroke_count.unwrap_or(u8::MAX))
+                .then(a.character.cmp(&b.character))
+        });
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::Radical;
+
+    fn get_radical(value: &str, stroke_count: u8) -> Radical {
+        Radical {
+            value: value.to_string(),
+            stroke_count,
+            meaning: "".to_string(),
+            pinyin: "".to_string(),
+        }
+    }
+
+    fn get_index() -> ComponentIndex {
+        let mut decompositions: HashMap<String, Decomposition> = HashMap::new();
+        let items = vec![
+            (
+                "娶",
+                vec![("耳", 6), ("又", 2), ("女", 3)],
+                vec!["取", "女"],
+            ),
+            ("好", vec![("女", 3), ("子", 3)], vec!["女", "子"]),
+            ("聂", vec![("耳", 6)], vec!["耳", "双"]),
+            ("耳", vec![("耳", 6)], vec!["二", "丨", "二"]),
+            ("姐", vec![("女", 3)], vec!["女"]),
+        ];
+
+        for (character, radicals, graphical) in items {
+            decompositions.insert(
+                character.to_string(),
+                Decomposition {
+                    radical: radicals
+                        .into_iter()
+                        .map(|(value, stroke_count)| get_radical(value, stroke_count))
+                        .collect(),
+                    graphical: graphical.into_iter().map(|pr| pr.to_string()).collect(),
+                    ids: match character {
+                        "好" => Some("⿰女子".to_string()),
+                        "娶" => Some("⿱取女".to_string()),
+                        "姐" => Some("⿰女且".to_string()),
+                        _ => None,
+                    },
+                },
+            );
+        }
+
+        let stroke_counts = vec![
+            ("娶", 11),
+            ("好", 6),
+            ("聂", 10),
+            ("耳", 6),
+            ("双", 4),
+            ("姐", 8),
+        ]
+        .into_iter()
+        .map(|(character, stroke_count)| (character.to_string(), stroke_count))
+        .collect();
+
+        ComponentIndex::new(&decompositions, stroke_counts)
+    }
+
+    fn get_characters(matches: &[ComponentMatch]) -> Vec<&str> {
+        matches.iter().map(|pr| pr.character.as_str()).collect()
+    }
+
+    #[test]
+    fn should_parse_components() {
+        assert_eq!(parse_components("女 耳,女"), vec!["女", "耳"]);
+    }
+
+    #[test]
+    fn should_find_characters_containing_all_components() {
+        let index = get_index();
+        let result = index.search(&parse_components("女耳"), None, None);
+        assert_eq!(get_characters(&result), vec!["娶"]);
+        assert_eq!(result[0].remaining_stroke_count, Some(2));
+
+        let result = index.search(&parse_components("女"), None, None);
+        assert_eq!(get_characters(&result), vec!["好", "姐", "娶"]);
+
+        let result = index.search(&parse_components("且"), None, None);
+        assert_eq!(get_characters(&result), vec!["姐"]);
+
+        assert!(index
+            .search(&parse_components("女火"), None, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn should_filter_by_remaining_stroke_count() {
+        let index = get_index();
+        let result = index.search(&parse_components("耳"), Some(4), None);
+        assert_eq!(get_characters(&result), vec!["聂"]);
+        let result = index.search(&parse_components("双"), Some(6), None);
+        assert_eq!(get_characters(&result), vec!["聂"]);
+    }
+
+    #[test]
+    fn should_filter_by_structure() {
+        let index = get_index();
+        let result = index.search(
+            &parse_components("女"),
+            None,
+            Some(IdsOperator::LeftToRight),
+        );
+        assert_eq!(get_characters(&result), vec!["好", "姐"]);
+        let result = index.search(
+            &parse_components("女"),
+            None,
+            Some(IdsOperator::AboveToBelow),
+        );
+        assert_eq!(get_characters(&result), vec!["娶"]);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::models::Group;
+use crate::models::HskLevel;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref PARENTHESES: Regex = Regex::new(r"\([^)]*\)").unwrap();
+}
+
+struct EnglishEntry {
+    group_index: usize,
+    detail_index: usize,
+    hsk_level: Option<u8>,
+    length: usize,
+}
+
+struct Gloss {
+    entry_index: usize,
+    position: usize,
+    is_literal: bool,
+    words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnglishMatch {
+    pub group_index: usize,
+    pub detail_index: usize,
+}
+
+pub struct EnglishIndex {
+    entries: Vec<EnglishEntry>,
+    glosses: Vec<Gloss>,
+    words: HashMap<String, Vec<usize>>,
+}
+
+/// Turns a gloss such as "to put (sth) in order" into ["put", "in", "order"].
+pub fn tokenize_gloss(value: &str) -> Vec<String> {
+    let value = PARENTHESES.replace_all(value, " ").to_lowercase();
+    let value = value.trim();
+    let value = value.strip_prefix("to ").unwrap_or(value);
+
+    value
+        .split(|pr: char| !pr.is_alphanumeric() && pr != '\'')
+        .filter(|pr| !pr.is_empty())
+        .map(|pr| pr.to_string())
+        .collect()
+}
+
+/// The level in the newest HSK version listing the word, so HSK 3.0 levels are
+/// used and HSK 2.0 ones only for words 3.0 dropped, never a mix of both.
+fn get_hsk_level(headwords: &[&str], hsk_levels: &[HskLevel]) -> Option<u8> {
+    let listed: Vec<&HskLevel> = hsk_levels
+        .iter()
+        .filter(|pr| {
+            headwords
+                .iter()
+                .any(|headword| pr.words.contains(*headword))
+        })
+        .collect();
+    let version = listed.iter().map(|pr| pr.version).max()?;
+
+    listed
+        .iter()
+        .filter(|pr| pr.version == version)
+        .map(|pr| pr.level)
+        .min()
+}
+
+fn contains_phrase(words: &[String], phrase: &[String]) -> bool {
+    words.windows(phrase.len()).any(|pr| pr == phrase)
+}
+
+impl EnglishIndex {
+    pub fn new(groups: &[Group], hsk_levels: &[HskLevel]) -> Self {
+        let mut entries: Vec<EnglishEntry> = Vec::with_capacity(groups.len() * 2);
+        let mut glosses: Vec<Gloss> = Vec::with_capacity(groups.len() * 3);
+        let mut words: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (group_index, group) in groups.iter().enumerate() {
+            for (detail_index, detail) in group.details.iter().enumerate() {
+                let entry_index = entries.len();
+                let headwords = [detail.simplified.as_str(), detail.traditional.as_str()];
+
+                entries.push(EnglishEntry {
+                    group_index,
+                    detail_index,
+                    hsk_level: get_hsk_level(&headwords, hsk_levels),
+                    length: detail.simplified.chars().count(),
+                });
+
+                for (position, meaning) in detail.meanings.iter().enumerate() {
+                    let values = [(&meaning.value, false), (&meaning.literal_meaning, true)];
+
+                    for (value, is_literal) in values.iter() {
+                        let value = match value {
+                            Some(value) => value,
+                            None => continue,
+                        };
+
+                        for item in value.split(';') {
+                            let tokens = tokenize_gloss(item);
+
+                            if tokens.is_empty() {
+                                continue;
+                            }
+
+                            for token in &tokens {
+                                let indices =
+                                    words.entry(token.to_owned()).or_insert_with(Vec::new);
+
+                                if indices.last() != Some(&glosses.len()) {
+                                    indices.push(glosses.len());
+                                }
+                            }
+
+                            glosses.push(Gloss {
+                                entry_index,
+                                position,
+                                is_literal: *is_literal,
+                                words: tokens,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            entries,
+            glosses,
+            words,
+        }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<EnglishMatch> {
+        let query = tokenize_gloss(query);
+
+        let candidates = query
+            .iter()
+            .map(|pr| self.words.get(pr).map(|pr| pr.as_slice()).unwrap_or(&[]))
+            .min_by_key(|pr| pr.len());
+
+        let candidates = match candidates {
+            Some(candidates) => candidates,
+            None => return Vec::new(),
+        };
+
+        let mut best: HashMap<usize, (bool, usize, bool, u8, usize, usize)> = HashMap::new();
+
+        for gloss_index in candidates {
+            let gloss = &self.glosses[*gloss_index];
+
+            if !contains_phrase(&gloss.words, &query) {
+                continue;
+            }
+
+            let entry = &self.entries[gloss.entry_index];
+            let rank = (
+                gloss.words.len() != query.len(),
+                gloss.position,
+                gloss.is_literal,
+                entry.hsk_level.unwrap_or(u8::MAX),
+                gloss.words.len(),
+                entry.length,
+            );
+
+            let current = best.entry(gloss.entry_index).or_insert(rank);
+
+            if rank < *current {
+                *current = rank;
+            }
+        }
+
+        let mut matches: Vec<(usize, _)> = best.into_iter().collect();
+        matches.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(entry_index, _)| EnglishMatch {
+                group_index: self.entries[entry_index].group_index,
+                detail_index: self.entries[entry_index].detail_index,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::*;
+    use std::collections::HashSet;
+
+    fn get_group(simplified: &str, meanings: &[&str]) -> Group {
+        Group {
+            simplified: simplified.to_string(),
+            details: vec![Detail {
+                id: simplified.to_string(),
+                simplified: simplified.to_string(),
+                traditional: simplified.to_string(),
+                meanings: meanings
+                    .iter()
+                    .map(|pr| Meaning {
+                        value: Some(pr.to_string()),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn search<'a>(groups: &'a [Group], index: &EnglishIndex, query: &str) -> Vec<&'a str> {
+        index
+            .search(query, 10)
+            .iter()
+            .map(|pr| groups[pr.group_index].simplified.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_tokenize_gloss() {
+        assert_eq!(
+            tokenize_gloss("to put (sth) in order"),
+            vec!["put", "in", "order"]
+        );
+        assert_eq!(tokenize_gloss("Beijing's (city)"), vec!["beijing's"]);
+        assert!(tokenize_gloss("(coll.)").is_empty());
+    }
+
+    #[test]
+    fn should_rank_by_position_and_hsk_level() {
+        let groups = vec![
+            get_group("地下室", &["basement", "cellar"]),
+            get_group("窖", &["cellar (for wine)"]),
+            get_group("地窖", &["cellar", "basement"]),
+            get_group("酒窖", &["wine cellar"]),
+        ];
+        let hsk_levels = vec![HskLevel {
+            tag: "hsk-3-7".to_string(),
+            version: 3,
+            level: 7,
+            words: vec!["地窖".to_string()].into_iter().collect::<HashSet<_>>(),
+        }];
+        let index = EnglishIndex::new(&groups, &hsk_levels);
+
+        assert_eq!(
+            search(&groups, &index, "cellar"),
+            vec!["地窖", "窖", "地下室", "酒窖"]
+        );
+        assert_eq!(search(&groups, &index, "wine cellar"), vec!["酒窖"]);
+    }
+
+    #[test]
+    fn should_prefer_newest_hsk_version() {
+        let get_hsk_level_record = |version: u8, level: u8, words: &[&str]| HskLevel {
+            tag: format!("hsk-{}-{}", version, level),
+            version,
+            level,
+            words: words.iter().map(|pr| pr.to_string()).collect(),
+        };
+        let hsk_levels = vec![
+            get_hsk_level_record(2, 1, &["地窖"]),
+            get_hsk_level_record(2, 3, &["酒窖"]),
+            get_hsk_level_record(3, 4, &["地窖"]),
+        ];
+
+        assert_eq!(get_hsk_level(&["地窖"], &hsk_levels), Some(4));
+        assert_eq!(get_hsk_level(&["酒窖"], &hsk_levels), Some(3));
+        assert_eq!(get_hsk_level(&["窖"], &hsk_levels), None);
+    }
+
+    #[test]
+    fn should_ignore_verb_prefix() {
+        let groups = vec![
+            get_group("跑", &["to run", "to escape"]),
+            get_group("运行", &["to be in motion", "to run (software)"]),
+        ];
+        let index = EnglishIndex::new(&groups, &[]);
+
+        assert_eq!(search(&groups, &index, "to run"), vec!["跑", "运行"]);
+        assert_eq!(search(&groups, &index, "software"), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod component_index;
+pub mod english_index;
+pub mod pinyin_index;
+pub mod similar_characters;
@@ -0,0 +1,469 @@
+use crate::models::Group;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Syllable {
+    pub value: String,
+    pub tone: u8,
+}
+
+struct PinyinEntry {
+    group_index: usize,
+    detail_index: usize,
+    pronunciation_index: usize,
+    length: usize,
+    syllables: Vec<Syllable>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinyinMatch {
+    pub group_index: usize,
+    pub detail_index: usize,
+    pub score: i32,
+}
+
+#[derive(Clone, Copy)]
+struct MatchResult {
+    fuzzy: usize,
+    partial: bool,
+    unmatched: usize,
+}
+
+impl MatchResult {
+    fn score(&self) -> i32 {
+        let mut score = 1000 - 100 * self.fuzzy as i32 - 40 * self.unmatched as i32;
+
+        if self.partial {
+            score = score - 50;
+        }
+
+        score
+    }
+}
+
+pub struct PinyinIndex {
+    entries: Vec<PinyinEntry>,
+    buckets: HashMap<char, Vec<usize>>,
+}
+
+fn strip_tone_mark(character: char) -> (char, Option<u8>) {
+    match character {
+        'ā' => ('a', Some(1)),
+        'á' => ('a', Some(2)),
+        'ǎ' => ('a', Some(3)),
+        'à' => ('a', Some(4)),
+        'ē' => ('e', Some(1)),
+        'é' => ('e', Some(2)),
+        'ě' => ('e', Some(3)),
+        'è' => ('e', Some(4)),
+        'ī' => ('i', Some(1)),
+        'í' => ('i', Some(2)),
+        'ǐ' => ('i', Some(3)),
+        'ì' => ('i', Some(4)),
+        'ō' => ('o', Some(1)),
+        'ó' => ('o', Some(2)),
+        'ǒ' => ('o', Some(3)),
+        'ò' => ('o', Some(4)),
+        'ū' => ('u', Some(1)),
+        'ú' => ('u', Some(2)),
+        'ǔ' => ('u', Some(3)),
+        'ù' => ('u', Some(4)),
+        'ǖ' => ('v', Some(1)),
+        'ǘ' => ('v', Some(2)),
+        'ǚ' => ('v', Some(3)),
+        'ǜ' => ('v', Some(4)),
+        'ü' => ('v', None),
+        'ń' => ('n', Some(2)),
+        'ň' => ('n', Some(3)),
+        'ǹ' => ('n', Some(4)),
+        'ḿ' => ('m', Some(2)),
+        _ => (character, None),
+    }
+}
+
+/// Splits a query such as "ni3hao3", "nǐ hǎo" or "nihao" into letters. A tone
+/// number is attached to the letter before it, a tone mark to its own vowel.
+pub fn parse_query(query: &str) -> Vec<(char, Option<u8>)> {
+    let mut letters: Vec<(char, Option<u8>)> = Vec::with_capacity(query.len());
+    let query = query.to_lowercase().replace("u:", "v");
+
+    for character in query.chars() {
+        if let Some(tone) = character.to_digit(10) {
+            if let Some(last) = letters.last_mut() {
+                if (1..=5).contains(&tone) {
+                    last.1 = Some(tone as u8);
+                }
+            }
+            continue;
+        }
+
+        let (letter, tone) = strip_tone_mark(character);
+
+        if letter.is_ascii_alphabetic() {
+            letters.push((letter, tone));
+        }
+    }
+
+    letters
+}
+
+pub fn parse_syllables(wade_giles_pinyin: &str, pinyin: &str) -> Vec<Syllable> {
+    let mut syllables: Vec<Syllable> = Vec::new();
+
+    if !wade_giles_pinyin.trim().is_empty() {
+        for item in wade_giles_pinyin.split_whitespace() {
+            let item = item.to_lowercase().replace("u:", "v").replace('ü', "v");
+            let value: String = item.chars().filter(|pr| pr.is_ascii_alphabetic()).collect();
+
+            if value.is_empty() {
+                continue;
+            }
+
+            let tone = item
+                .chars()
+                .filter_map(|pr| pr.to_digit(10))
+                .last()
+                .filter(|pr| (1..=5).contains(pr))
+                .unwrap_or(0) as u8;
+
+            syllables.push(Syllable { value, tone });
+        }
+
+        return syllables;
+    }
+
+    for item in pinyin.split_whitespace() {
+        let letters = parse_query(item);
+
+        if letters.is_empty() {
+            continue;
+        }
+
+        syllables.push(Syllable {
+            value: letters.iter().map(|pr| pr.0).collect(),
+            tone: letters.iter().find_map(|pr| pr.1).unwrap_or(5),
+        });
+    }
+
+    syllables
+}
+
+/// Spellings a learner may type for a syllable. The second value marks a
+/// fuzzy spelling: zh/z, ch/c, sh/s, -ng/-n and ü/u are treated as confusable.
+fn get_variants(value: &str) -> Vec<(String, bool)> {
+    let mut variants: Vec<(String, bool)> = vec![(value.to_string(), false)];
+
+    let initials: &[(&str, &str)] = &[("zh", "z"), ("ch", "c"), ("sh", "s")];
+
+    for (retroflex, dental) in initials {
+        if let Some(rest) = value.strip_prefix(retroflex) {
+            variants.push((format!("{}{}", dental, rest), true));
+        } else if let Some(rest) = value.strip_prefix(dental) {
+            variants.push((format!("{}{}", retroflex, rest), true));
+        }
+    }
+
+    let count = variants.len();
+
+    for index in 0..count {
+        let variant = variants[index].0.to_owned();
+
+        if let Some(rest) = variant.strip_suffix("ng") {
+            variants.push((format!("{}n", rest), true));
+        } else if variant.ends_with("an") || variant.ends_with("en") || variant.ends_with("in") {
+            variants.push((format!("{}g", variant), true));
+        }
+    }
+
+    let count = variants.len();
+
+    for index in 0..count {
+        let variant = variants[index].0.to_owned();
+
+        if variant.contains('v') {
+            variants.push((variant.replace('v', "u"), true));
+        }
+    }
+
+    variants
+}
+
+fn is_tone_matching(query: &[(char, Option<u8>)], tone: u8) -> bool {
+    tone == 0 || query.iter().filter_map(|pr| pr.1).all(|pr| pr == tone)
+}
+
+fn match_syllables(
+    query: &[(char, Option<u8>)],
+    syllables: &[Syllable],
+    fuzzy: bool,
+) -> Option<MatchResult> {
+    if query.is_empty() {
+        return Some(MatchResult {
+            fuzzy: 0,
+            partial: false,
+            unmatched: syllables.len(),
+        });
+    }
+
+    let syllable = syllables.first()?;
+    let mut best: Option<MatchResult> = None;
+
+    for (variant, is_fuzzy) in get_variants(&syllable.value) {
+        if is_fuzzy && !fuzzy {
+            continue;
+        }
+
+        let letters: Vec<char> = variant.chars().collect();
+        let result = if query.len() >= letters.len() {
+            let consumed = &query[..letters.len()];
+            let is_matching = consumed.iter().map(|pr| pr.0).eq(letters.iter().cloned());
+
+            if !is_matching || !is_tone_matching(consumed, syllable.tone) {
+                continue;
+            }
+
+            match_syllables(&query[letters.len()..], &syllables[1..], fuzzy)
+        } else {
+            let is_matching = query
+                .iter()
+                .map(|pr| pr.0)
+                .eq(letters[..query.len()].iter().cloned());
+
+            if !is_matching || !is_tone_matching(query, syllable.tone) {
+                continue;
+            }
+
+            Some(MatchResult {
+                fuzzy: 0,
+                partial: true,
+                unmatched: syllables.len() - 1,
+            })
+        };
+
+        if let Some(mut result) = result {
+            if is_fuzzy {
+                result.fuzzy = result.fuzzy + 1;
+            }
+
+            if best.map(|pr| result.score() > pr.score()).unwrap_or(true) {
+                best = Some(result);
+            }
+        }
+    }
+
+    best
+}
+
+impl PinyinIndex {
+    pub fn new(groups: &[Group]) -> Self {
+        let mut entries: Vec<PinyinEntry> = Vec::with_capacity(groups.len() * 2);
+        let mut buckets: HashMap<char, Vec<usize>> = HashMap::new();
+
+        for (group_index, group) in groups.iter().enumerate() {
+            for (detail_index, detail) in group.details.iter().enumerate() {
+                for (pronunciation_index, pronunciation) in detail.pronunciation.iter().enumerate()
+                {
+                    let syllables =
+                        parse_syllables(&pronunciation.wade_giles_pinyin, &pronunciation.pinyin);
+
+                    let first = match syllables.first().and_then(|pr| pr.value.chars().next()) {
+                        Some(first) => first,
+                        None => continue,
+                    };
+
+                    buckets
+                        .entry(first)
+                        .or_insert_with(Vec::new)
+                        .push(entries.len());
+                    entries.push(PinyinEntry {
+                        group_index,
+                        detail_index,
+                        pronunciation_index,
+                        length: detail.simplified.chars().count(),
+                        syllables,
+                    });
+                }
+            }
+        }
+
+        Self { entries, buckets }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<PinyinMatch> {
+        let query = parse_query(query);
+
+        let first = match query.first() {
+            Some(first) => first.0,
+            None => return Vec::new(),
+        };
+
+        let candidates = self
+            .buckets
+            .get(&first)
+            .map(|pr| pr.as_slice())
+            .unwrap_or(&[]);
+        let mut matches: Vec<(i32, usize, usize, usize)> = Vec::new();
+        let mut seen: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for index in candidates {
+            let entry = &self.entries[*index];
+
+            let result = match match_syllables(&query, &entry.syllables, true) {
+                Some(result) => result,
+                None => continue,
+            };
+
+            let score = result.score() - 10 * entry.pronunciation_index as i32;
+            let key = (entry.group_index, entry.detail_index);
+
+            match seen.get(&key) {
+                Some(position) if matches[*position].0 >= score => {}
+                Some(position) => matches[*position].0 = score,
+                None => {
+                    seen.insert(key, matches.len());
+                    matches.push((score, entry.length, entry.group_index, entry.detail_index));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(a.1.cmp(&b.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+        });
+
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(score, _, group_index, detail_index)| PinyinMatch {
+                group_index,
+                detail_index,
+                score,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::*;
+
+    fn get_group(simplified: &str, pronunciations: &[(&str, &str)]) -> Group {
+        Group {
+            simplified: simplified.to_string(),
+            details: vec![Detail {
+                id: simplified.to_string(),
+                pronunciation: pronunciations
+                    .iter()
+                    .map(|pr| Pronunciation {
+                        pinyin: pr.0.to_string(),
+                        wade_giles_pinyin: pr.1.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                simplified: simplified.to_string(),
+                traditional: simplified.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn get_index() -> (Vec<Group>, PinyinIndex) {
+        let groups = vec![
+            get_group("你", &[("nǐ", "ni3")]),
+            get_group("你好", &[("nǐ hǎo", "ni3 hao3")]),
+            get_group("你好吗", &[("nǐ hǎo ma", "ni3 hao3 ma5")]),
+            get_group("泥淖", &[("ní nào", "ni2 nao4")]),
+            get_group("中国", &[("Zhōng guó", "zhong1 guo2")]),
+            get_group("女", &[("nǚ", "nv3")]),
+            get_group("西安", &[("Xī ān", "xi1 an1")]),
+            get_group("先", &[("xiān", "xian1")]),
+        ];
+        let index = PinyinIndex::new(&groups);
+        (groups, index)
+    }
+
+    fn search<'a>(groups: &'a [Group], index: &PinyinIndex, query: &str) -> Vec<&'a str> {
+        index
+            .search(query, 10)
+            .iter()
+            .map(|pr| groups[pr.group_index].simplified.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_parse_query() {
+        assert_eq!(
+            parse_query("Nǐ hao3"),
+            vec![
+                ('n', None),
+                ('i', Some(3)),
+                ('h', None),
+                ('a', None),
+                ('o', Some(3))
+            ]
+        );
+        assert_eq!(parse_query("lu:4"), vec![('l', None), ('v', Some(4))]);
+    }
+
+    #[test]
+    fn should_parse_syllables() {
+        assert_eq!(
+            parse_syllables("Lu:4 shi1", ""),
+            vec![
+                Syllable {
+                    value: "lv".to_string(),
+                    tone: 4
+                },
+                Syllable {
+                    value: "shi".to_string(),
+                    tone: 1
+                }
+            ]
+        );
+        assert_eq!(parse_syllables("", "nǐ hǎo")[1].tone, 3);
+    }
+
+    #[test]
+    fn should_ignore_tones_spaces_and_diacritics() {
+        let (groups, index) = get_index();
+
+        for query in &["nihao", "ni3hao3", "ni hao", "nǐhǎo", "NI3 HAO"] {
+            assert_eq!(search(&groups, &index, query)[0], "你好", "{}", query);
+        }
+    }
+
+    #[test]
+    fn should_respect_tones() {
+        let (groups, index) = get_index();
+        let result = search(&groups, &index, "ni2");
+        assert_eq!(result, vec!["泥淖"]);
+    }
+
+    #[test]
+    fn should_search_by_prefix() {
+        let (groups, index) = get_index();
+        let result = search(&groups, &index, "nih");
+        assert_eq!(result, vec!["你好", "你好吗"]);
+        let result = search(&groups, &index, "ni");
+        assert_eq!(result[0], "你");
+    }
+
+    #[test]
+    fn should_handle_confusions() {
+        let (groups, index) = get_index();
+        assert_eq!(search(&groups, &index, "zonguo")[0], "中国");
+        assert_eq!(search(&groups, &index, "nu3")[0], "女");
+    }
+
+    #[test]
+    fn should_segment_ambiguous_syllables() {
+        let (groups, index) = get_index();
+        let result = search(&groups, &index, "xian");
+        assert_eq!(result[0], "先");
+        assert!(result.contains(&"西安"));
+    }
+}
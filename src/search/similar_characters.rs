@@ -0,0 +1,201 @@
+use crate::models::Decomposition;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+static MINIMUM_SCORE: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarCharacter {
+    pub character: String,
+    pub score: f64,
+}
+
+pub struct SimilarityIndex {
+    graphical: HashMap<String, Vec<String>>,
+    characters: HashMap<String, BTreeSet<String>>,
+    stroke_counts: HashMap<String, u8>,
+}
+
+/// Dice coefficient over the component lists, counting repeated components
+/// (二 twice in 耳) as separate items.
+fn get_overlap(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let mut remaining: Vec<&String> = b.iter().collect();
+    let mut shared = 0;
+
+    for component in a {
+        if let Some(position) = remaining.iter().position(|pr| *pr == component) {
+            remaining.swap_remove(position);
+            shared = shared + 1;
+        }
+    }
+
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+impl SimilarityIndex {
+    pub fn new(
+        decompositions: &HashMap<String, Decomposition>,
+        stroke_counts: HashMap<String, u8>,
+    ) -> Self {
+        let mut graphical: HashMap<String, Vec<String>> = HashMap::new();
+        let mut characters: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+        for (character, decomposition) in decompositions {
+            let components: Vec<String> = decomposition
+                .graphical
+                .iter()
+                .filter(|pr| !pr.is_empty() && *pr != character)
+                .cloned()
+                .collect();
+
+            if components.is_empty() {
+                continue;
+            }
+
+            for component in &components {
+                characters
+                    .entry(component.to_owned())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(character.to_owned());
+            }
+
+            graphical.insert(character.to_owned(), components);
+        }
+
+        Self {
+            graphical,
+            characters,
+            stroke_counts,
+        }
+    }
+
+    /// Mostly the overlap of the graphical components, so that 未 and 末 score
+    /// 1 and 己 and 巳 score 0.8, nudged down when the stroke counts differ.
+    pub fn get_similarity(&self, a: &str, b: &str) -> f64 {
+        let overlap = match (self.graphical.get(a), self.graphical.get(b)) {
+            (Some(a), Some(b)) => get_overlap(a, b),
+            _ => return 0.0,
+        };
+
+        let strokes = match (self.stroke_counts.get(a), self.stroke_counts.get(b)) {
+            (Some(a), Some(b)) => {
+                let difference = (*a as f64 - *b as f64).abs();
+                1.0 - difference / (*a).max(*b).max(1) as f64
+            }
+            _ => overlap,
+        };
+
+        0.8 * overlap + 0.2 * strokes
+    }
+
+    /// The `similar` field of a refined entry, only set for single characters.
+    pub fn get_similar_headwords(&self, headword: &str, limit: usize) -> Option<Vec<String>> {
+        if headword.chars().count() != 1 {
+            return None;
+        }
+
+        let result: Vec<String> = self
+            .find_similar(headword, limit)
+            .into_iter()
+            .map(|pr| pr.character)
+            .collect();
+
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    pub fn find_similar(&self, character: &str, limit: usize) -> Vec<SimilarCharacter> {
+        let components = match self.graphical.get(character) {
+            Some(components) => components,
+            None => return Vec::new(),
+        };
+
+        let mut candidates: BTreeSet<&String> = BTreeSet::new();
+
+        for component in components {
+            if let Some(items) = self.characters.get(component) {
+                candidates.extend(items.iter().filter(|pr| *pr != character));
+            }
+        }
+
+        let mut result: Vec<SimilarCharacter> = candidates
+            .into_iter()
+            .map(|pr| SimilarCharacter {
+                character: pr.to_owned(),
+                score: self.get_similarity(character, pr),
+            })
+            .filter(|pr| pr.score >= MINIMUM_SCORE)
+            .collect();
+
+        result.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.character.cmp(&b.character))
+        });
+        result.truncate(limit);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::get_decomposition_from_file::get_decomposition_from_file;
+    use crate::utils::get_stroke_order_map;
+    use std::env;
+
+    fn get_index() -> SimilarityIndex {
+        let assets_directory = env::current_dir().unwrap().join("assets");
+        let decompositions = get_decomposition_from_file(
+            &assets_directory.join("character-decomposition.txt"),
+            &HashMap::new(),
+        )
+        .unwrap();
+        let stroke_counts =
+            get_stroke_order_map(&assets_directory.join("stroke-order.txt")).unwrap();
+
+        SimilarityIndex::new(&decompositions, stroke_counts)
+    }
+
+    fn get_characters(result: &[SimilarCharacter]) -> Vec<&str> {
+        result.iter().map(|pr| pr.character.as_str()).collect()
+    }
+
+    #[test]
+    fn should_get_overlap() {
+        let a: Vec<String> = vec!["二".to_string(), "丨".to_string(), "二".to_string()];
+        let b: Vec<String> = vec!["二".to_string(), "丨".to_string()];
+        assert!((get_overlap(&a, &b) - 0.8).abs() < 1e-9);
+        assert!((get_overlap(&a, &a) - 1.0).abs() < 1e-9);
+        assert_eq!(get_overlap(&a, &[]), 0.0);
+    }
+
+    #[test]
+    fn should_find_similar_characters() {
+        let index = get_index();
+
+        let result = index.find_similar("未", 5);
+        assert_eq!(result[0].character, "末");
+        assert!((result[0].score - 1.0).abs() < 1e-9);
+
+        assert!(get_characters(&index.find_similar("己", 10)).contains(&"巳"));
+        assert!(get_characters(&index.find_similar("土", 10)).contains(&"士"));
+        assert!(index.find_similar("x", 10).is_empty());
+
+        assert_eq!(
+            index.get_similar_headwords("未", 1),
+            Some(vec!["末".to_string()])
+        );
+        assert_eq!(index.get_similar_headwords("未来", 1), None);
+    }
+}
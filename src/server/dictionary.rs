@@ -0,0 +1,207 @@
+use crate::models::*;
+use crate::refiner::build_decomposition_tree::build_decomposition_tree;
+use crate::refiner::parse_ids::parse_ids;
+use crate::search::component_index::ComponentIndex;
+use crate::search::component_index::ComponentMatch;
+use crate::search::english_index::EnglishIndex;
+use crate::search::pinyin_index::PinyinIndex;
+use std::collections::HashMap;
+
+pub struct Dictionary {
+    pub groups: Vec<Group>,
+    headwords: HashMap<String, Vec<usize>>,
+    decompositions: HashMap<String, Decomposition>,
+    component_table: ComponentTable,
+    pinyin_index: PinyinIndex,
+    english_index: EnglishIndex,
+    component_index: ComponentIndex,
+}
+
+impl Dictionary {
+    pub fn new(
+        groups: Vec<Group>,
+        decompositions: HashMap<String, Decomposition>,
+        component_table: ComponentTable,
+        hsk_levels: &[HskLevel],
+    ) -> Self {
+        let mut headwords: HashMap<String, Vec<usize>> = HashMap::with_capacity(groups.len() * 2);
+        let mut stroke_counts: HashMap<String, u8> = HashMap::new();
+
+        for (index, group) in groups.iter().enumerate() {
+            headwords
+                .entry(group.simplified.to_owned())
+                .or_insert_with(Vec::new)
+                .push(index);
+
+            for detail in &group.details {
+                if let Some(stroke_count) = detail.simplified_stroke_count {
+                    stroke_counts.insert(detail.simplified.to_owned(), stroke_count);
+                }
+
+                if let Some(stroke_count) = detail.traditional_stroke_count {
+                    stroke_counts.insert(detail.traditional.to_owned(), stroke_count);
+                }
+
+                let indices = headwords
+                    .entry(detail.traditional.to_owned())
+                    .or_insert_with(Vec::new);
+
+                if !indices.contains(&index) {
+                    indices.push(index);
+                }
+            }
+        }
+
+        let pinyin_index = PinyinIndex::new(&groups);
+        let english_index = EnglishIndex::new(&groups, hsk_levels);
+        let component_index = ComponentIndex::new(&decompositions, stroke_counts);
+
+        Self {
+            groups,
+            headwords,
+            decompositions,
+            component_table,
+            pinyin_index,
+            english_index,
+            component_index,
+        }
+    }
+
+    pub fn lookup(&self, headword: &str) -> Vec<&Group> {
+        self.headwords
+            .get(headword)
+            .map(|pr| pr.iter().map(|index| &self.groups[*index]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get_decomposition(&self, character: &str) -> Option<&Decomposition> {
+        self.decompositions.get(character)
+    }
+
+    pub fn get_structure(&self, character: &str) -> Option<IdsNode> {
+        let ids = self.decompositions.get(character)?.ids.as_ref()?;
+        parse_ids(ids).ok()
+    }
+
+    pub fn get_decomposition_tree(&self, character: &str) -> Option<DecompositionTree> {
+        if !self.component_table.components.contains_key(character) {
+            return None;
+        }
+
+        Some(build_decomposition_tree(character, &self.component_table))
+    }
+
+    pub fn search_components(
+        &self,
+        components: &[String],
+        remaining_stroke_count: Option<u8>,
+        structure: Option<IdsOperator>,
+    ) -> Vec<ComponentMatch> {
+        self.component_index
+            .search(components, remaining_stroke_count, structure)
+    }
+
+    pub fn search_pinyin(&self, query: &str, limit: usize) -> Vec<&Detail> {
+        self.pinyin_index
+            .search(query, limit)
+            .iter()
+            .map(|pr| &self.groups[pr.group_index].details[pr.detail_index])
+            .collect()
+    }
+
+    pub fn search_english(&self, query: &str, limit: usize) -> Vec<&Detail> {
+        self.english_index
+            .search(query, limit)
+            .iter()
+            .map(|pr| &self.groups[pr.group_index].details[pr.detail_index])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_detail(
+        simplified: &str,
+        traditional: &str,
+        pinyin: (&str, &str),
+        meanings: &[&str],
+    ) -> Detail {
+        Detail {
+            id: simplified.to_string(),
+            pronunciation: vec![Pronunciation {
+                pinyin: pinyin.0.to_string(),
+                wade_giles_pinyin: pinyin.1.to_string(),
+                ..Default::default()
+            }],
+            simplified: simplified.to_string(),
+            traditional: traditional.to_string(),
+            meanings: meanings
+                .iter()
+                .map(|pr| Meaning {
+                    value: Some(pr.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn get_dictionary() -> Dictionary {
+        let details = vec![
+            get_detail("你好", "你好", ("nǐ hǎo", "ni3 hao3"), &["hello", "hi"]),
+            get_detail(
+                "地窖",
+                "地窖",
+                ("dì jiào", "di4 jiao4"),
+                &["cellar", "basement"],
+            ),
+            get_detail(
+                "地下室",
+                "地下室",
+                ("dì xià shì", "di4 xia4 shi4"),
+                &["basement", "cellar"],
+            ),
+            get_detail("书", "書", ("shū", "shu1"), &["book"]),
+        ];
+        let groups = details
+            .into_iter()
+            .map(|pr| Group {
+                simplified: pr.simplified.to_owned(),
+                details: vec![pr],
+                ..Default::default()
+            })
+            .collect();
+
+        Dictionary::new(groups, HashMap::new(), ComponentTable::default(), &[])
+    }
+
+    #[test]
+    fn should_lookup_simplified_and_traditional() {
+        let dictionary = get_dictionary();
+        assert_eq!(dictionary.lookup("书")[0].simplified, "书");
+        assert_eq!(dictionary.lookup("書")[0].simplified, "书");
+        assert!(dictionary.lookup("x").is_empty());
+    }
+
+    #[test]
+    fn should_search_pinyin_with_and_without_tones() {
+        let dictionary = get_dictionary();
+        assert_eq!(
+            dictionary.search_pinyin("ni3 hao3", 10)[0].simplified,
+            "你好"
+        );
+        assert_eq!(dictionary.search_pinyin("nihao", 10)[0].simplified, "你好");
+        assert_eq!(dictionary.search_pinyin("nǐ hǎo", 10)[0].simplified, "你好");
+        assert!(dictionary.search_pinyin("ni2hao3", 10).is_empty());
+    }
+
+    #[test]
+    fn should_search_english() {
+        let dictionary = get_dictionary();
+        let result = dictionary.search_english("cellar", 10);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].simplified, "地窖");
+    }
+}
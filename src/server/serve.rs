@@ -0,0 +1,294 @@
+use crate::models::IdsOperator;
+use crate::search::component_index::parse_components;
+use crate::server::dictionary::Dictionary;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use tiny_http::{Header, Method, Response, Server};
+use urlencoding::decode;
+
+const SEARCH_LIMIT: usize = 50;
+
+static ROUTES: [&str; 7] = [
+    "/lookup",
+    "/search/pinyin",
+    "/search/english",
+    "/structure",
+    "/decomposition/tree",
+    "/components",
+    "/decomposition",
+];
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    let mut parameters: HashMap<String, String> = HashMap::new();
+
+    for pair in query.split('&').filter(|pr| !pr.is_empty()) {
+        let mut parts = pair.splitn(2, '=');
+        let key = parts.next().unwrap_or_default().replace('+', " ");
+        let value = parts.next().unwrap_or_default().replace('+', " ");
+
+        let key = decode(&key).map(|pr| pr.into_owned()).unwrap_or(key);
+        let value = decode(&value).map(|pr| pr.into_owned()).unwrap_or(value);
+
+        parameters.insert(key, value);
+    }
+
+    parameters
+}
+
+fn to_json<T: Serialize>(status: u16, value: &T) -> (u16, String) {
+    match serde_json::to_string(value) {
+        Ok(body) => (status, body),
+        Err(error) => error_response(500, &error.to_string()),
+    }
+}
+
+fn error_response(status: u16, message: &str) -> (u16, String) {
+    let body = serde_json::to_string(&ErrorResponse {
+        error: message.to_string(),
+    })
+    .unwrap_or_default();
+
+    (status, body)
+}
+
+pub fn handle_request(dictionary: &Dictionary, method: &Method, url: &str) -> (u16, String) {
+    if *method != Method::Get {
+        return error_response(405, "method not allowed");
+    }
+
+    let mut parts = url.splitn(2, '?');
+    let path = parts.next().unwrap_or_default();
+
+    if !ROUTES.contains(&path) {
+        return error_response(404, "not found");
+    }
+
+    let parameters = parse_query(parts.next().unwrap_or_default());
+    let query = parameters.get("q").map(|pr| pr.trim()).unwrap_or_default();
+
+    if query.is_empty() {
+        return error_response(400, "missing query parameter q");
+    }
+
+    match path {
+        "/lookup" => to_json(200, &dictionary.lookup(query)),
+        "/search/pinyin" => to_json(200, &dictionary.search_pinyin(query, SEARCH_LIMIT)),
+        "/search/english" => to_json(200, &dictionary.search_english(query, SEARCH_LIMIT)),
+        "/structure" => match dictionary.get_structure(query) {
+            Some(structure) => to_json(200, &structure),
+            None => error_response(404, "structure not found"),
+        },
+        "/decomposition/tree" => match dictionary.get_decomposition_tree(query) {
+            Some(tree) => to_json(200, &tree),
+            None => error_response(404, "decomposition not found"),
+        },
+        "/components" => {
+            let remaining_stroke_count = match parameters.get("strokes") {
+                Some(value) => match value.trim().parse::<u8>() {
+                    Ok(value) => Some(value),
+                    Err(_) => return error_response(400, "invalid query parameter strokes"),
+                },
+                None => None,
+            };
+
+            let structure = match parameters.get("structure") {
+                Some(value) => {
+                    match serde_json::from_value::<IdsOperator>(Value::String(value.to_owned())) {
+                        Ok(value) => Some(value),
+                        Err(_) => return error_response(400, "invalid query parameter structure"),
+                    }
+                }
+                None => None,
+            };
+
+            to_json(
+                200,
+                &dictionary.search_components(
+                    &parse_components(query),
+                    remaining_stroke_count,
+                    structure,
+                ),
+            )
+        }
+        "/decomposition" => match dictionary.get_decomposition(query) {
+            Some(decomposition) => to_json(200, decomposition),
+            None => error_response(404, "decomposition not found"),
+        },
+        _ => error_response(404, "not found"),
+    }
+}
+
+pub fn serve(dictionary: &Dictionary, address: &str) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(address).map_err(|pr| pr.to_string())?;
+    let content_type = Header::from_bytes(
+        &b"Content-Type"[..],
+        &b"application/json; charset=utf-8"[..],
+    )
+    .unwrap();
+
+    info!("Listening on http://{}", address);
+
+    for request in server.incoming_requests() {
+        let (status, body) = handle_request(dictionary, request.method(), request.url());
+        debug!("{} {} {}", request.method(), request.url(), status);
+
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(content_type.clone());
+
+        if let Err(error) = request.respond(response) {
+            warn!("Could not respond: {}", error);
+            continue;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::*;
+
+    fn get_dictionary() -> Dictionary {
+        let detail = Detail {
+            id: "1".to_string(),
+            pronunciation: vec![Pronunciation {
+                pinyin: "dì jiào".to_string(),
+                wade_giles_pinyin: "di4 jiao4".to_string(),
+                ..Default::default()
+            }],
+            simplified: "地窖".to_string(),
+            traditional: "地窖".to_string(),
+            meanings: vec![Meaning {
+                value: Some("cellar".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let groups = vec![Group {
+            simplified: "地窖".to_string(),
+            details: vec![detail],
+            ..Default::default()
+        }];
+        let mut decompositions: HashMap<String, Decomposition> = HashMap::new();
+        decompositions.insert(
+            "窖".to_string(),
+            Decomposition {
+                radical: Vec::new(),
+                graphical: vec!["穴".to_string(), "告".to_string()],
+                ids: Some("⿱穴告".to_string()),
+            },
+        );
+
+        let mut component_table = ComponentTable::default();
+        component_table.components.insert(
+            "窖".to_string(),
+            vec![Some("穴".to_string()), Some("告".to_string())],
+        );
+
+        Dictionary::new(groups, decompositions, component_table, &[])
+    }
+
+    #[test]
+    fn should_parse_query() {
+        let parameters = parse_query("q=ni+hao&lang=%E5%9C%B0");
+        assert_eq!(parameters["q"], "ni hao");
+        assert_eq!(parameters["lang"], "地");
+    }
+
+    #[test]
+    fn should_handle_lookup() {
+        let dictionary = get_dictionary();
+        let (status, body) =
+            handle_request(&dictionary, &Method::Get, "/lookup?q=%E5%9C%B0%E7%AA%96");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value[0]["simplified"], "地窖");
+        assert_eq!(value[0]["details"][0]["id"], "1");
+    }
+
+    #[test]
+    fn should_handle_searches() {
+        let dictionary = get_dictionary();
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/search/pinyin?q=dijiao");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value[0]["simplified"], "地窖");
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/search/english?q=cellar");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value[0]["simplified"], "地窖");
+    }
+
+    #[test]
+    fn should_handle_decomposition() {
+        let dictionary = get_dictionary();
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/decomposition?q=窖");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value["graphical"][0], "穴");
+
+        let (status, _) = handle_request(&dictionary, &Method::Get, "/decomposition?q=x");
+        assert_eq!(status, 404);
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/decomposition/tree?q=窖");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value["status"], "decomposed");
+        assert_eq!(value["components"][1]["value"], "告");
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/structure?q=窖");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value["operator"], "above-to-below");
+        assert_eq!(value["components"][0], "穴");
+    }
+
+    #[test]
+    fn should_handle_components() {
+        let dictionary = get_dictionary();
+
+        let (status, body) = handle_request(&dictionary, &Method::Get, "/components?q=穴,告");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value[0]["character"], "窖");
+
+        let (status, body) = handle_request(
+            &dictionary,
+            &Method::Get,
+            "/components?q=穴&structure=above-to-below",
+        );
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(value[0]["character"], "窖");
+
+        let (status, _) = handle_request(&dictionary, &Method::Get, "/components?q=穴&strokes=x");
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn should_reject_invalid_requests() {
+        let dictionary = get_dictionary();
+        assert_eq!(handle_request(&dictionary, &Method::Get, "/lookup").0, 400);
+        assert_eq!(
+            handle_request(&dictionary, &Method::Get, "/unknown?q=a").0,
+            404
+        );
+        assert_eq!(handle_request(&dictionary, &Method::Get, "/unknown").0, 404);
+        assert_eq!(
+            handle_request(&dictionary, &Method::Post, "/lookup?q=a").0,
+            405
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::models::CERecord;
+use crate::models::PinyinMap;
+use crate::refiner::to_pinyin::to_pinyin;
+use crate::text::segmenter::Segmenter;
+use crate::utils::escape_html::escape_html;
+use crate::utils::get_detail_id::get_record_id;
+use crate::utils::get_detail_id::get_record_pinyin;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotatedToken {
+    pub value: String,
+    pub start: usize,
+    pub ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Simplified headword of the record `id` points to, so that text in
+    /// traditional characters can be looked up in simplified-only lists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simplified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinyin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gloss: Option<String>,
+}
+
+pub struct Annotator {
+    segmenter: Segmenter,
+    records: HashMap<String, CERecord>,
+    pinyin_map: HashMap<String, PinyinMap>,
+}
+
+/// Orders the readings of a word so that the everyday one comes first: proper
+/// nouns and entries that only point at another word are pushed back, and the
+/// reading with more senses wins among the rest.
+fn get_reading_rank(record: &CERecord) -> (bool, bool, Reverse<usize>) {
+    let is_proper_noun = get_record_pinyin(record)
+        .chars()
+        .next()
+        .map(|pr| pr.is_uppercase())
+        .unwrap_or_default();
+
+    let is_reference = record.meanings.iter().all(|pr| {
+        pr.starts_with("surname ")
+            || pr.starts_with("variant of ")
+            || pr.starts_with("old variant of ")
+            || pr.starts_with("see ")
+            || pr.starts_with("used in ")
+    });
+
+    (is_proper_noun, is_reference, Reverse(record.meanings.len()))
+}
+
+impl Annotator {
+    pub fn new(records: &[CERecord], pinyin_map: HashMap<String, PinyinMap>) -> Self {
+        Self {
+            segmenter: Segmenter::new(records),
+            records: records
+                .iter()
+                .map(|pr| (get_record_id(pr), pr.clone()))
+                .collect(),
+            pinyin_map,
+        }
+    }
+
+    pub fn annotate(&self, text: &str) -> Vec<AnnotatedToken> {
+        self.segmenter
+            .segment(text)
+            .into_iter()
+            .map(|token| {
+                let record = token
+                    .ids
+                    .iter()
+                    .filter_map(|pr| self.records.get(pr))
+                    .min_by_key(|pr| get_reading_rank(pr));
+
+                AnnotatedToken {
+                    id: record.map(get_record_id),
+                    simplified: record.map(|pr| pr.simplified.to_owned()),
+                    pinyin: record.map(|pr| to_pinyin(&pr.wade_giles_pinyin, &self.pinyin_map)),
+                    gloss: record.and_then(|pr| pr.meanings.first().cloned()),
+                    value: token.value,
+                    start: token.start,
+                    ids: token.ids,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Puts each syllable over its own character when they line up, otherwise the
+/// whole reading goes over the word.
+pub fn render_ruby_html(tokens: &[AnnotatedToken]) -> String {
+    let mut result = String::new();
+
+    for token in tokens {
+        let pinyin = match &token.pinyin {
+            Some(pinyin) => pinyin,
+            None => {
+                result = result + &escape_html(&token.value);
+                continue;
+            }
+        };
+
+        let characters: Vec<char> = token.value.chars().collect();
+        let syllables: Vec<&str> = pinyin.split_whitespace().collect();
+
+        let body = if characters.len() == syllables.len() {
+            characters
+                .iter()
+                .zip(syllables.iter())
+                .map(|(character, syllable)| {
+                    format!(
+                        "{}<rt>{}</rt>",
+                        escape_html(&character.to_string()),
+                        escape_html(syllable)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("")
+        } else {
+            format!(
+                "{}<rt>{}</rt>",
+                escape_html(&token.value),
+                escape_html(&syllables.join(""))
+            )
+        };
+
+        result = result
+            + &format!(
+                "<ruby title=\"{}\">{}</ruby>",
+                escape_html(token.gloss.as_deref().unwrap_or_default()),
+                body
+            );
+    }
+
+    result
+}
+
+pub fn render_plain_text(tokens: &[AnnotatedToken]) -> String {
+    let mut result = String::new();
+
+    for token in tokens {
+        result = result + &token.value;
+
+        if let Some(pinyin) = &token.pinyin {
+            result = result + &format!("({})", pinyin.split_whitespace().collect::<String>());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::parse_ce_record::parse_ce_record;
+
+    fn get_record(
+        traditional: &str,
+        simplified: &str,
+        pinyin: &str,
+        meanings: &[&str],
+    ) -> CERecord {
+        let line = format!(
+            "{} {} [{}] /{}/\n",
+            traditional,
+            simplified,
+            pinyin,
+            meanings.join("/")
+        );
+
+        parse_ce_record(&line, 0)
+    }
+
+    fn get_annotator() -> Annotator {
+        let records = vec![
+            get_record("我", "我", "wo3", &["I", "me"]),
+            get_record("去", "去", "qu4", &["to go"]),
+            get_record("銀行", "银行", "yin2 hang2", &["bank"]),
+            get_record(
+                "行",
+                "行",
+                "Xing2",
+                &["Xing county", "Xing river", "Xing mountain", "Xing lake"],
+            ),
+            get_record("行", "行", "hang2", &["row", "line"]),
+            get_record("行", "行", "xing2", &["to walk", "to go", "capable"]),
+        ];
+        let pinyin_map = vec![
+            ("wo3", "wǒ"),
+            ("qu4", "qù"),
+            ("yin2", "yín"),
+            ("hang2", "háng"),
+            ("xing2", "xíng"),
+        ]
+        .into_iter()
+        .map(|(wade_giles, pinyin)| {
+            (
+                wade_giles.to_string(),
+                PinyinMap {
+                    pinyin: pinyin.to_string(),
+                    wade_giles: wade_giles.to_string(),
+                },
+            )
+        })
+        .collect();
+
+        Annotator::new(&records, pinyin_map)
+    }
+
+    #[test]
+    fn should_disambiguate_by_word() {
+        let annotator = get_annotator();
+        let tokens = annotator.annotate("我去银行。行！");
+
+        let pinyins: Vec<Option<&str>> = tokens.iter().map(|pr| pr.pinyin.as_deref()).collect();
+        assert_eq!(
+            pinyins,
+            vec![
+                Some("wǒ"),
+                Some("qù"),
+                Some("yín háng"),
+                None,
+                Some("xíng"),
+                None
+            ]
+        );
+        assert_eq!(tokens[2].gloss.as_deref(), Some("bank"));
+        assert_eq!(tokens[4].ids.len(), 3);
+        assert_eq!(tokens[4].gloss.as_deref(), Some("to walk"));
+    }
+
+    #[test]
+    fn should_render_ruby_html() {
+        let annotator = get_annotator();
+        let tokens = annotator.annotate("银行a<b");
+        assert_eq!(
+            render_ruby_html(&tokens),
+            "<ruby title=\"bank\">银<rt>yín</rt>行<rt>háng</rt></ruby>a&lt;b"
+        );
+    }
+
+    #[test]
+    fn should_render_plain_text() {
+        let annotator = get_annotator();
+        let tokens = annotator.annotate("我去银行。");
+        assert_eq!(render_plain_text(&tokens), "我(wǒ)去(qù)银行(yínháng)。");
+    }
+}
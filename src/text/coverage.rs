@@ -0,0 +1,310 @@
+use crate::models::HskLevel;
+use crate::text::annotate::AnnotatedToken;
+use crate::utils::is_cjk::is_cjk;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+static COMPREHENSION_THRESHOLD: f64 = 0.95;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelCoverage {
+    pub tag: String,
+    pub version: u8,
+    pub level: u8,
+    pub count: usize,
+    pub share: f64,
+    pub cumulative_share: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutOfSyllabusWord {
+    pub value: String,
+    pub count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinyin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gloss: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Difficulty {
+    pub version: u8,
+    pub level: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    pub token_count: usize,
+    pub levels: Vec<LevelCoverage>,
+    pub out_of_syllabus: Vec<OutOfSyllabusWord>,
+    pub difficulty: Vec<Difficulty>,
+}
+
+/// The HSK lists are in simplified characters, so a token is looked up by the
+/// simplified headword it was matched to.
+fn get_headword(token: &AnnotatedToken) -> &str {
+    token.simplified.as_deref().unwrap_or(&token.value)
+}
+
+fn get_level(value: &str, version: u8, hsk_levels: &[HskLevel]) -> Option<u8> {
+    hsk_levels
+        .iter()
+        .filter(|pr| pr.version == version && pr.words.contains(value))
+        .map(|pr| pr.level)
+        .min()
+}
+
+/// Only tokens containing Chinese characters are counted, so punctuation and
+/// latin text do not dilute the shares. The estimated difficulty is the lowest
+/// level at which 95% of the tokens are known, or none when the syllabus
+/// never gets there.
+pub fn get_coverage_report(tokens: &[AnnotatedToken], hsk_levels: &[HskLevel]) -> CoverageReport {
+    let words: Vec<&AnnotatedToken> = tokens
+        .iter()
+        .filter(|pr| pr.value.chars().any(|character| is_cjk(&character)))
+        .collect();
+
+    let token_count = words.len();
+    let mut versions: Vec<u8> = hsk_levels.iter().map(|pr| pr.version).collect();
+    versions.sort_unstable();
+    versions.dedup();
+
+    let mut levels: Vec<LevelCoverage> = Vec::with_capacity(hsk_levels.len());
+    let mut difficulty: Vec<Difficulty> = Vec::with_capacity(versions.len());
+
+    for version in &versions {
+        let mut counts: HashMap<u8, usize> = HashMap::new();
+
+        for word in &words {
+            if let Some(level) = get_level(get_headword(word), *version, hsk_levels) {
+                *counts.entry(level).or_insert(0) += 1;
+            }
+        }
+
+        let mut version_levels: Vec<&HskLevel> = hsk_levels
+            .iter()
+            .filter(|pr| pr.version == *version)
+            .collect();
+        version_levels.sort_by_key(|pr| pr.level);
+
+        let mut cumulative = 0;
+        let mut estimated: Option<u8> = None;
+
+        for hsk_level in version_levels {
+            let count = counts.get(&hsk_level.level).cloned().unwrap_or_default();
+            cumulative = cumulative + count;
+
+            let share = get_share(count, token_count);
+            let cumulative_share = get_share(cumulative, token_count);
+
+            if estimated.is_none() && token_count > 0 && cumulative_share >= COMPREHENSION_THRESHOLD
+            {
+                estimated = Some(hsk_level.level);
+            }
+
+            levels.push(LevelCoverage {
+                tag: hsk_level.tag.to_owned(),
+                version: hsk_level.version,
+                level: hsk_level.level,
+                count,
+                share,
+                cumulative_share,
+            });
+        }
+
+        difficulty.push(Difficulty {
+            version: *version,
+            level: estimated,
+        });
+    }
+
+    let mut out_of_syllabus: Vec<OutOfSyllabusWord> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+
+    for word in &words {
+        let headword = get_headword(word);
+
+        if hsk_levels.iter().any(|pr| pr.words.contains(headword)) {
+            continue;
+        }
+
+        match positions.get(word.value.as_str()) {
+            Some(position) => out_of_syllabus[*position].count += 1,
+            None => {
+                positions.insert(&word.value, out_of_syllabus.len());
+                out_of_syllabus.push(OutOfSyllabusWord {
+                    value: word.value.to_owned(),
+                    count: 1,
+                    pinyin: word.pinyin.to_owned(),
+                    gloss: word.gloss.to_owned(),
+                });
+            }
+        }
+    }
+
+    out_of_syllabus.sort_by(|a, b| b.count.cmp(&a.count));
+
+    CoverageReport {
+        token_count,
+        levels,
+        out_of_syllabus,
+        difficulty,
+    }
+}
+
+fn get_share(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    count as f64 / total as f64
+}
+
+pub fn render_coverage_report(report: &CoverageReport) -> String {
+    let mut result = format!("Tokens: {}\n", report.token_count);
+
+    for version in &report.difficulty {
+        let level = version
+            .level
+            .map(|pr| pr.to_string())
+            .unwrap_or_else(|| "beyond syllabus".to_string());
+        result = result + &format!("Estimated HSK {}.0 level: {}\n", version.version, level);
+    }
+
+    result = result + "\nCoverage\n";
+
+    for level in &report.levels {
+        result = result
+            + &format!(
+                "{}: {} tokens, {:.1}%, {:.1}% cumulative\n",
+                level.tag,
+                level.count,
+                level.share * 100.0,
+                level.cumulative_share * 100.0
+            );
+    }
+
+    result = result + "\nOut of syllabus\n";
+
+    for word in &report.out_of_syllabus {
+        result = result
+            + &format!(
+                "{} ({}) x{}: {}\n",
+                word.value,
+                word.pinyin.as_deref().unwrap_or_default(),
+                word.count,
+                word.gloss.as_deref().unwrap_or_default()
+            );
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::text::annotate::Annotator;
+    use crate::utils::get_hsk_levels_from_directory::get_hsk_levels_from_directory;
+    use crate::utils::parse_ce_record::parse_ce_record;
+    use std::env;
+
+    fn get_token(value: &str, gloss: Option<&str>) -> AnnotatedToken {
+        AnnotatedToken {
+            value: value.to_string(),
+            start: 0,
+            ids: Vec::new(),
+            id: None,
+            simplified: None,
+            pinyin: None,
+            gloss: gloss.map(|pr| pr.to_string()),
+        }
+    }
+
+    fn get_hsk_level(version: u8, level: u8, words: &[&str]) -> HskLevel {
+        HskLevel {
+            tag: format!("hsk-{}-{}", version, level),
+            version,
+            level,
+            words: words.iter().map(|pr| pr.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn should_report_coverage() {
+        let hsk_levels = vec![
+            get_hsk_level(2, 1, &["我", "去"]),
+            get_hsk_level(2, 2, &["银行"]),
+            get_hsk_level(3, 1, &["我", "去", "银行"]),
+        ];
+        let tokens = vec![
+            get_token("我", None),
+            get_token("去", None),
+            get_token("银行", None),
+            get_token("兑换", Some("to exchange")),
+            get_token("。", None),
+            get_token("兑换", Some("to exchange")),
+        ];
+
+        let report = get_coverage_report(&tokens, &hsk_levels);
+        assert_eq!(report.token_count, 5);
+        assert_eq!(report.levels.len(), 3);
+        assert_eq!(report.levels[1].count, 1);
+        assert!((report.levels[1].cumulative_share - 0.6).abs() < 1e-9);
+        assert_eq!(report.out_of_syllabus.len(), 1);
+        assert_eq!(report.out_of_syllabus[0].count, 2);
+        assert_eq!(
+            report.out_of_syllabus[0].gloss.as_deref(),
+            Some("to exchange")
+        );
+        assert_eq!(
+            report.difficulty,
+            vec![
+                Difficulty {
+                    version: 2,
+                    level: None
+                },
+                Difficulty {
+                    version: 3,
+                    level: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn should_estimate_difficulty() {
+        let assets_directory = env::current_dir().unwrap().join("assets");
+        let hsk_levels = get_hsk_levels_from_directory(&assets_directory).unwrap();
+        let tokens: Vec<AnnotatedToken> = vec!["我", "是", "学生", "你", "好"]
+            .into_iter()
+            .map(|pr| get_token(pr, None))
+            .collect();
+
+        let report = get_coverage_report(&tokens, &hsk_levels);
+        assert!(report.out_of_syllabus.is_empty());
+        assert_eq!(report.difficulty[0].level, Some(1));
+        assert!(render_coverage_report(&report).contains("Estimated HSK 2.0 level: 1\n"));
+    }
+
+    #[test]
+    fn should_look_up_traditional_text_by_simplified_headword() {
+        let records: Vec<_> = vec![
+            "我 我 [wo3] /I/",
+            "去 去 [qu4] /to go/",
+            "銀行 银行 [yin2 hang2] /bank/",
+            "兌換 兑换 [dui4 huan4] /to exchange/",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, line)| parse_ce_record(&format!("{}\n", line), index as u32))
+        .collect();
+        let annotator = Annotator::new(&records, HashMap::new());
+        let hsk_levels = vec![get_hsk_level(3, 1, &["我", "去", "银行"])];
+
+        let report = get_coverage_report(&annotator.annotate("我去銀行兌換。"), &hsk_levels);
+        assert_eq!(report.token_count, 4);
+        assert_eq!(report.levels[0].count, 3);
+        assert_eq!(report.out_of_syllabus.len(), 1);
+        assert_eq!(report.out_of_syllabus[0].value, "兌換");
+    }
+}
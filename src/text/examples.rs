@@ -0,0 +1,159 @@
+use crate::models::Example;
+use crate::text::segmenter::Segmenter;
+use crate::utils::is_cjk::is_cjk;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+struct Candidate {
+    unknown: usize,
+    total: usize,
+    length: usize,
+    position: usize,
+}
+
+impl Candidate {
+    /// Compares the shares of unknown words without going through floats.
+    fn compare(&self, other: &Candidate) -> Ordering {
+        (self.unknown * other.total)
+            .cmp(&(other.unknown * self.total))
+            .then(self.length.cmp(&other.length))
+            .then(self.position.cmp(&other.position))
+    }
+}
+
+/// Segments every sentence and keeps, for each detail id found in it, the
+/// `limit` best sentences: the ones with the larger share of words in
+/// `known_ids` first, then the shorter ones.
+pub fn get_examples_by_id(
+    sentences: &[Example],
+    segmenter: &Segmenter,
+    known_ids: &HashSet<String>,
+    limit: usize,
+) -> HashMap<String, Vec<Example>> {
+    let mut candidates: HashMap<String, Vec<Candidate>> = HashMap::new();
+
+    for (position, sentence) in sentences.iter().enumerate() {
+        let tokens: Vec<_> = segmenter
+            .segment(&sentence.value)
+            .into_iter()
+            .filter(|pr| pr.value.chars().any(|character| is_cjk(&character)))
+            .collect();
+
+        let unknown = tokens
+            .iter()
+            .filter(|pr| !pr.ids.iter().any(|id| known_ids.contains(id)))
+            .count();
+        let length = sentence.value.chars().count();
+        let mut ids: Vec<&String> = tokens.iter().flat_map(|pr| pr.ids.iter()).collect();
+        ids.sort();
+        ids.dedup();
+
+        for id in ids {
+            candidates
+                .entry(id.to_owned())
+                .or_insert_with(Vec::new)
+                .push(Candidate {
+                    unknown,
+                    total: tokens.len(),
+                    length,
+                    position,
+                });
+        }
+    }
+
+    candidates
+        .into_iter()
+        .map(|(id, mut list)| {
+            list.sort_unstable_by(|a, b| a.compare(b));
+
+            let examples = list
+                .iter()
+                .take(limit)
+                .map(|pr| sentences[pr.position].clone())
+                .collect();
+
+            (id, examples)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::CERecord;
+    use crate::models::Translation;
+    use crate::utils::get_detail_id::get_record_id;
+    use crate::utils::parse_ce_record::parse_ce_record;
+
+    fn get_example(id: u32, value: &str) -> Example {
+        Example {
+            id,
+            value: value.to_string(),
+            translations: vec![Translation {
+                language: "en".to_string(),
+                value: "-".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn should_prefer_short_known_sentences() {
+        let records: Vec<CERecord> = vec![
+            "我 我 [wo3] /I/",
+            "喜歡 喜欢 [xi3 huan5] /to like/",
+            "你 你 [ni3] /you/",
+            "貓 猫 [mao1] /cat/",
+            "鸚鵡 鹦鹉 [ying1 wu3] /parrot/",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, line)| parse_ce_record(&format!("{}\n", line), index as u32))
+        .collect();
+        let segmenter = Segmenter::new(&records);
+        let known_ids: HashSet<String> = records[..4].iter().map(get_record_id).collect();
+        let sentences = vec![
+            get_example(1, "我喜欢鹦鹉。"),
+            get_example(2, "我喜歡你，你喜歡貓。"),
+            get_example(3, "我喜欢猫。"),
+        ];
+
+        let examples = get_examples_by_id(&sentences, &segmenter, &known_ids, 2);
+        let like = &examples[&get_record_id(&records[1])];
+
+        assert_eq!(like.len(), 2);
+        assert_eq!(like[0].id, 3);
+        assert_eq!(like[1].id, 2);
+        assert_eq!(examples[&get_record_id(&records[4])][0].id, 1);
+    }
+
+    #[test]
+    fn should_prefer_share_of_known_words_over_count() {
+        let records: Vec<CERecord> = vec![
+            "我 我 [wo3] /I/",
+            "喜歡 喜欢 [xi3 huan5] /to like/",
+            "你 你 [ni3] /you/",
+            "貓 猫 [mao1] /cat/",
+            "鸚鵡 鹦鹉 [ying1 wu3] /parrot/",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, line)| parse_ce_record(&format!("{}\n", line), index as u32))
+        .collect();
+        let segmenter = Segmenter::new(&records);
+        let known_ids: HashSet<String> = records[..4].iter().map(get_record_id).collect();
+        let sentences = vec![
+            get_example(1, "我喜欢鹦鹉。"),
+            get_example(2, "我喜欢你，你喜欢猫，猫喜欢鹦鹉，鹦鹉喜欢我。"),
+            get_example(3, "你喜欢鹦鹉。"),
+        ];
+
+        let examples = get_examples_by_id(&sentences, &segmenter, &known_ids, 3);
+        let like: Vec<u32> = examples[&get_record_id(&records[1])]
+            .iter()
+            .map(|pr| pr.id)
+            .collect();
+
+        assert_eq!(like, vec![2, 1, 3]);
+    }
+}
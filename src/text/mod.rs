@@ -0,0 +1,4 @@
+pub mod annotate;
+pub mod coverage;
+pub mod examples;
+pub mod segmenter;
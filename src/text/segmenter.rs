@@ -0,0 +1,212 @@
+use crate::models::CERecord;
+use crate::utils::get_detail_id::get_record_id;
+use crate::utils::is_cjk::is_cjk;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Token {
+    pub value: String,
+    pub start: usize,
+    pub ids: Vec<String>,
+}
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    ids: Vec<String>,
+}
+
+pub struct Segmenter {
+    nodes: Vec<Node>,
+}
+
+fn is_word_character(character: &char) -> bool {
+    character.is_alphanumeric() && !is_cjk(character)
+}
+
+impl Segmenter {
+    pub fn new(records: &[CERecord]) -> Self {
+        let mut segmenter = Self {
+            nodes: vec![Node::default()],
+        };
+
+        for record in records {
+            let id = get_record_id(record);
+            segmenter.insert(&record.simplified, &id);
+
+            if record.traditional != record.simplified {
+                segmenter.insert(&record.traditional, &id);
+            }
+        }
+
+        segmenter
+    }
+
+    fn insert(&mut self, headword: &str, id: &str) {
+        let mut current = 0;
+
+        for character in headword.chars() {
+            current = match self.nodes[current].children.get(&character) {
+                Some(next) => *next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[current].children.insert(character, next);
+                    next
+                }
+            };
+        }
+
+        let ids = &mut self.nodes[current].ids;
+
+        if !ids.iter().any(|pr| pr == id) {
+            ids.push(id.to_string());
+        }
+    }
+
+    /// Every dictionary word starting at `start`, as (end, node) pairs.
+    fn get_words(&self, characters: &[char], start: usize) -> Vec<(usize, usize)> {
+        let mut words = Vec::new();
+        let mut current = 0;
+
+        for (offset, character) in characters[start..].iter().enumerate() {
+            current = match self.nodes[current].children.get(character) {
+                Some(next) => *next,
+                None => break,
+            };
+
+            if !self.nodes[current].ids.is_empty() {
+                words.push((start + offset + 1, current));
+            }
+        }
+
+        words
+    }
+
+    /// Splits text into dictionary words. The path through the word graph is
+    /// chosen to leave the fewest characters unknown, then to use the fewest
+    /// tokens, then to keep word lengths even (研究|生命 over 研究生|命).
+    /// Characters outside the dictionary become single tokens, except runs of
+    /// latin letters and digits which stay together.
+    pub fn segment(&self, text: &str) -> Vec<Token> {
+        let characters: Vec<char> = text.chars().collect();
+        let length = characters.len();
+
+        let mut best: Vec<(usize, usize, usize)> = vec![(0, 0, 0); length + 1];
+        let mut next: Vec<(usize, Option<usize>)> = vec![(0, None); length + 1];
+
+        for start in (0..length).rev() {
+            let mut end = start + 1;
+
+            if is_word_character(&characters[start]) {
+                while end < length && is_word_character(&characters[end]) {
+                    end = end + 1;
+                }
+            }
+
+            let (unknown, count, spread) = best[end];
+            best[start] = (unknown + 1, count + 1, spread + 1);
+            next[start] = (end, None);
+
+            for (end, node) in self.get_words(&characters, start) {
+                let (unknown, count, spread) = best[end];
+                let score = (unknown, count + 1, spread + (end - start).pow(2));
+
+                if score <= best[start] {
+                    best[start] = score;
+                    next[start] = (end, Some(node));
+                }
+            }
+        }
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        while start < length {
+            let (end, node) = next[start];
+
+            tokens.push(Token {
+                value: characters[start..end].iter().collect(),
+                start,
+                ids: node
+                    .map(|pr| self.nodes[pr].ids.clone())
+                    .unwrap_or_default(),
+            });
+
+            start = end;
+        }
+
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::DEFAULT_LANGUAGE;
+
+    fn get_record(traditional: &str, simplified: &str, wade_giles_pinyin: &str) -> CERecord {
+        CERecord {
+            line_number: 0,
+            line: format!("{} {} [{}] /x/", traditional, simplified, wade_giles_pinyin),
+            simplified: simplified.to_string(),
+            traditional: traditional.to_string(),
+            wade_giles_pinyin: wade_giles_pinyin.to_string(),
+            meanings: Vec::new(),
+            language: DEFAULT_LANGUAGE.to_string(),
+        }
+    }
+
+    fn get_segmenter() -> Segmenter {
+        Segmenter::new(&[
+            get_record("研究", "研究", "yan2 jiu1"),
+            get_record("研究生", "研究生", "yan2 jiu1 sheng1"),
+            get_record("生命", "生命", "sheng1 ming4"),
+            get_record("命", "命", "ming4"),
+            get_record("起源", "起源", "qi3 yuan2"),
+            get_record("的", "的", "de5"),
+            get_record("的", "的", "di4"),
+            get_record("學習", "学习", "xue2 xi2"),
+        ])
+    }
+
+    fn get_values(tokens: &[Token]) -> Vec<&str> {
+        tokens.iter().map(|pr| pr.value.as_str()).collect()
+    }
+
+    #[test]
+    fn should_prefer_fewer_words() {
+        let segmenter = get_segmenter();
+        let tokens = segmenter.segment("研究生命的起源");
+        assert_eq!(get_values(&tokens), vec!["研究", "生命", "的", "起源"]);
+        assert_eq!(tokens[1].start, 2);
+        assert_eq!(tokens[2].ids.len(), 2);
+    }
+
+    #[test]
+    fn should_match_simplified_and_traditional() {
+        let segmenter = get_segmenter();
+        let simplified = segmenter.segment("学习");
+        let traditional = segmenter.segment("學習");
+        assert_eq!(simplified.len(), 1);
+        assert_eq!(simplified[0].ids, traditional[0].ids);
+        assert_eq!(
+            simplified[0].ids,
+            vec![get_record_id(&get_record("學習", "学习", "xue2 xi2"))]
+        );
+    }
+
+    #[test]
+    fn should_fall_back_to_single_characters() {
+        let segmenter = get_segmenter();
+        let tokens = segmenter.segment("我学习Rust 2021。");
+        assert_eq!(
+            get_values(&tokens),
+            vec!["我", "学习", "Rust", " ", "2021", "。"]
+        );
+        assert!(tokens[0].ids.is_empty());
+        assert!(tokens[2].ids.is_empty());
+        assert!(tokens[5].ids.is_empty());
+    }
+}
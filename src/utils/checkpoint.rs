@@ -0,0 +1,118 @@
+use crate::customReader::custom_reader::BufReader;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::prelude::*;
+use std::io::LineWriter;
+use std::path::Path;
+
+/// Output of a long running scrape that is written line by line, so that a
+/// crash loses at most the line in flight. Keys are the first field of each
+/// line, up to a ';' or a space. Keys that failed go to the retry list, which
+/// is started over on every run.
+pub struct Checkpoint {
+    processed: HashSet<String>,
+    line_writer: LineWriter<File>,
+    retry_writer: LineWriter<File>,
+    pub retry_count: usize,
+}
+
+fn get_key(line: &str) -> &str {
+    line.split(|pr| pr == ';' || pr == ' ')
+        .next()
+        .unwrap_or_default()
+        .trim()
+}
+
+impl Checkpoint {
+    /// When resuming, lines already in `file_path` are kept and their keys
+    /// reported as processed; otherwise the file is truncated.
+    pub fn open(file_path: &Path, retry_path: &Path, resume: bool) -> Result<Self, Box<dyn Error>> {
+        let mut processed: HashSet<String> = HashSet::new();
+
+        if resume && file_path.exists() {
+            for line in BufReader::open(file_path)? {
+                let line = line?;
+                let key = get_key(&line);
+
+                if !key.is_empty() {
+                    processed.insert(key.to_owned());
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resume)
+            .truncate(!resume)
+            .open(file_path)?;
+
+        Ok(Self {
+            processed,
+            line_writer: LineWriter::new(file),
+            retry_writer: LineWriter::new(File::create(retry_path)?),
+            retry_count: 0,
+        })
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.processed.contains(key)
+    }
+
+    pub fn write_line(&mut self, line: &str) -> Result<(), Box<dyn Error>> {
+        self.line_writer
+            .write_all(format!("{}\n", line.trim_end()).as_bytes())?;
+        self.processed.insert(get_key(line).to_owned());
+
+        Ok(())
+    }
+
+    pub fn add_retry(&mut self, key: &str, error: &dyn Error) -> Result<(), Box<dyn Error>> {
+        warn!("Could not process {}: {}", key, error);
+        self.retry_writer
+            .write_all(format!("{}\n", key).as_bytes())?;
+        self.retry_count = self.retry_count + 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_resume_from_checkpoint() {
+        let file_path = env::temp_dir().join("refined-cedict-json-checkpoint.txt");
+        let retry_path = env::temp_dir().join("refined-cedict-json-checkpoint.retry.txt");
+
+        let mut checkpoint = Checkpoint::open(&file_path, &retry_path, false).unwrap();
+        checkpoint.write_line("要;覀, 女").unwrap();
+        checkpoint
+            .add_retry("好", &*Box::<dyn Error>::from("timeout"))
+            .unwrap();
+        assert!(checkpoint.contains("要"));
+        drop(checkpoint);
+
+        let mut checkpoint = Checkpoint::open(&file_path, &retry_path, true).unwrap();
+        assert!(checkpoint.contains("要"));
+        assert!(!checkpoint.contains("好"));
+        checkpoint.write_line("好 6").unwrap();
+        drop(checkpoint);
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "要;覀, 女\n好 6\n");
+        assert_eq!(fs::read_to_string(&retry_path).unwrap(), "");
+
+        let checkpoint = Checkpoint::open(&file_path, &retry_path, false).unwrap();
+        assert!(!checkpoint.contains("要"));
+        drop(checkpoint);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "");
+
+        fs::remove_file(&file_path).unwrap();
+        fs::remove_file(&retry_path).unwrap();
+    }
+}
@@ -0,0 +1,36 @@
+pub fn escape_html(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        match character {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(character),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_escape_html() {
+        let result = escape_html("<b>\"Tom & Jerry's\"</b>");
+        assert_eq!(
+            result,
+            "&lt;b&gt;&quot;Tom &amp; Jerry&#39;s&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn should_keep_cjk() {
+        let result = escape_html("地窖");
+        assert_eq!(result, "地窖");
+    }
+}
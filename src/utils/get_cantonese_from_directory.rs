@@ -0,0 +1,122 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::CERecord;
+use crate::models::Cantonese;
+use crate::utils::get_detail_id::get_detail_id;
+use crate::utils::parse_ce_record::normalize_ce_line;
+use crate::utils::parse_ce_record::parse_ce_record;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::error::Error;
+use std::path::Path;
+
+/// CC-Canto with definitions, then the CC-CEDICT Cantonese readings, which
+/// only add a `{jyutping}` field to CC-CEDICT headwords.
+static FILE_NAMES: [&str; 2] = ["cccanto-webdist.txt", "cccedict-canto-readings.txt"];
+
+/// The detail id, the Jyutping and, when the line has definitions, the record.
+pub fn parse_cantonese_line(
+    line: &str,
+    line_number: u32,
+) -> Option<(String, String, Option<CERecord>)> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(
+            r"^(?P<traditional>\S+)\s(?P<simplified>\S+)\s\[(?P<pinyin>[^\]]*)\]\s(?P<jyutping>\{[^}]*\})",
+        )
+        .unwrap();
+    }
+
+    let line = normalize_ce_line(line);
+    let captures = REGEX.captures(&line)?;
+    let jyutping = captures.name("jyutping").unwrap();
+    let id = get_detail_id(
+        captures.name("traditional").unwrap().as_str(),
+        captures.name("simplified").unwrap().as_str(),
+        captures.name("pinyin").unwrap().as_str(),
+    );
+
+    let rest = &line[jyutping.end()..];
+    let record = if rest.trim_start().starts_with('/') {
+        let line = format!("{}{}", &line[..jyutping.start()], rest.trim_start());
+        Some(parse_ce_record(&line, line_number))
+    } else {
+        None
+    };
+
+    let jyutping = jyutping.as_str().trim_matches(|pr| pr == '{' || pr == '}');
+
+    Some((id, jyutping.trim().to_string(), record))
+}
+
+/// Reads whichever of the CC-Canto files are in `assets_directory`.
+pub fn get_cantonese_from_directory(assets_directory: &Path) -> Result<Cantonese, Box<dyn Error>> {
+    let mut cantonese = Cantonese::default();
+
+    for file_name in &FILE_NAMES {
+        let file_path = assets_directory.join(file_name);
+
+        if !file_path.exists() {
+            continue;
+        }
+
+        let lines = BufReader::open(file_path)?;
+        let mut line_number = 0;
+
+        for line in lines {
+            let line = line?;
+            line_number = line_number + 1;
+
+            if line.starts_with("#") {
+                continue;
+            }
+
+            let (id, jyutping, record) = match parse_cantonese_line(&line, line_number) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+
+            let readings = cantonese.readings.entry(id).or_insert_with(Vec::new);
+
+            if !jyutping.is_empty() && !readings.contains(&jyutping) {
+                readings.push(jyutping);
+            }
+
+            if let Some(record) = record {
+                cantonese.records.push(record);
+            }
+        }
+    }
+
+    Ok(cantonese)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::get_detail_id::get_record_id;
+
+    #[test]
+    fn should_parse_cc_canto_line() {
+        let line = "嘅 嘅 [ge4] {ge3} /(possessive particle, like 的)/\n";
+        let (id, jyutping, record) = parse_cantonese_line(line, 3).unwrap();
+        let record = record.unwrap();
+
+        assert_eq!(jyutping, "ge3");
+        assert_eq!(record.simplified, "嘅");
+        assert_eq!(record.wade_giles_pinyin, "ge4");
+        assert_eq!(record.meanings, vec!["(possessive particle, like 的)"]);
+        assert_eq!(record.line_number, 3);
+        assert_eq!(id, get_record_id(&record));
+    }
+
+    #[test]
+    fn should_parse_readings_line() {
+        let line = "綠 绿 [lu:4] {luk6}\n";
+        let (id, jyutping, record) = parse_cantonese_line(line, 1).unwrap();
+        let cedict = parse_ce_record("綠 绿 [lu:4] /green/\n", 1);
+
+        assert_eq!(jyutping, "luk6");
+        assert!(record.is_none());
+        assert_eq!(id, get_record_id(&cedict));
+        assert!(parse_cantonese_line("綠 绿 [lu:4] /green/\n", 1).is_none());
+    }
+}
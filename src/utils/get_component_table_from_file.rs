@@ -0,0 +1,85 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::ComponentTable;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::error::Error;
+use std::path::Path;
+
+static NO_GLYPH: &str = "No glyph available";
+static NOT_AVAILABLE: &str = "N/A";
+
+pub fn get_component_table_from_file(file_path: &Path) -> Result<ComponentTable, Box<dyn Error>> {
+    lazy_static! {
+        static ref MEANING_REGEX: Regex = Regex::new(r"^(\S+)\s*\((.*)\)$").unwrap();
+    }
+
+    let lines = BufReader::open(file_path)?;
+    let mut table = ComponentTable::default();
+
+    for line in lines {
+        let line = line?;
+        let parts: Vec<&str> = line.trim().split(';').collect();
+
+        if parts.len() == 1 {
+            continue;
+        }
+
+        if parts.len() < 4 {
+            return Err("invalid file format".into());
+        }
+
+        let components: Vec<Option<String>> = parts[1]
+            .split(',')
+            .map(|pr| pr.trim())
+            .filter(|pr| !pr.is_empty())
+            .map(|pr| {
+                if pr == NO_GLYPH {
+                    None
+                } else {
+                    Some(pr.to_string())
+                }
+            })
+            .collect();
+
+        for item in parts[2].split(',') {
+            if let Some(captures) = MEANING_REGEX.captures(item.trim()) {
+                let meaning = captures[2].trim();
+
+                if meaning != NOT_AVAILABLE && !meaning.is_empty() {
+                    table
+                        .meanings
+                        .entry(captures[1].to_string())
+                        .or_insert_with(|| meaning.to_string());
+                }
+            }
+        }
+
+        table
+            .components
+            .insert(parts[0].trim().to_owned(), components);
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn should_get_component_table_from_file() {
+        let assets_directory = env::current_dir().unwrap().join("assets");
+        let table =
+            get_component_table_from_file(&assets_directory.join("character-decomposition.txt"))
+                .unwrap();
+
+        assert_eq!(
+            table.components["娶"],
+            vec![Some("取".to_string()), Some("女".to_string())]
+        );
+        assert_eq!(table.components["塍"][1], None);
+        assert_eq!(table.meanings["女"], "woman");
+        assert!(!table.meanings.contains_key("㇒"));
+    }
+}
@@ -8,9 +8,11 @@ use std::error::Error;
 use std::io::prelude::*;
 use std::path::Path;
 
+/// A shared radical form such as 阝 adds every radical it belongs to, since the
+/// file doesn't say which one is meant.
 pub fn get_decomposition_from_file(
     file_path: &Path,
-    radical_lookup: &HashMap<String, Radical>,
+    radical_lookup: &HashMap<String, Vec<Radical>>,
 ) -> Result<HashMap<String, Decomposition>, Box<dyn Error>> {
     lazy_static! {
         static ref EXTRACT_REGEX: Regex = Regex::new(r"(\(.*?\))|\s").unwrap();
@@ -26,20 +28,64 @@ pub fn get_decomposition_from_file(
         let parts: Vec<&str> = linee.split(";").collect();
 
         if parts.len() == 1 {
+            continue;
+        }
+
+        if parts.len() < 4 {
             return Err("invalid file format".into());
         }
 
         let radical: Vec<Radical> = parts[2]
             .split(",")
-            .map(|pr| radical_lookup.get(pr).unwrap().to_owned())
+            .filter_map(|pr| radical_lookup.get(pr))
+            .flatten()
+            .cloned()
             .collect();
 
         let graphical: Vec<String> = parts[3].split(",").map(|pr| pr.to_string()).collect();
 
-        let record = Decomposition { radical, graphical };
+        let record = Decomposition {
+            radical,
+            graphical,
+            ids: None,
+        };
 
         dict.insert(parts[0].to_owned(), record);
     }
 
     Ok(dict)
 }
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn should_get_decomposition_from_file() {
+        let current_directory = env::current_dir().unwrap();
+        let assets_directory = current_directory.join("assets");
+        let mut radical_lookup: HashMap<String, Vec<Radical>> = HashMap::new();
+        radical_lookup.insert(
+            "舟".to_string(),
+            vec![Radical {
+                value: "舟".to_string(),
+                stroke_count: 6,
+                meaning: "boat".to_string(),
+                pinyin: "zhōu".to_string(),
+            }],
+        );
+
+        let result = get_decomposition_from_file(
+            &assets_directory.join("character-decomposition.txt"),
+            &radical_lookup,
+        )
+        .unwrap();
+
+        let decomposition = &result["艋"];
+        assert_eq!(decomposition.radical, radical_lookup["舟"]);
+        assert_eq!(decomposition.graphical[0], "冂");
+        assert!(result.contains_key("玙"));
+    }
+}
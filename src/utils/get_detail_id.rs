@@ -0,0 +1,72 @@
+use crate::models::CERecord;
+use crate::utils::get_hash::get_hash;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Lines with the same traditional, simplified and pinyin get the same id;
+/// callers that need one detail per id have to merge them.
+pub fn get_detail_id(traditional: &str, simplified: &str, wade_giles_pinyin: &str) -> String {
+    let pinyin: Vec<&str> = wade_giles_pinyin.split_whitespace().collect();
+    let key = format!("{} {} [{}]", traditional, simplified, pinyin.join(" "));
+
+    format!("{:016x}", get_hash(&key))
+}
+
+/// The pinyin as written in the source line. `wade_giles_pinyin` is
+/// lowercased, which would merge entries like 华 [Hua2] and 华 [hua2].
+pub fn get_record_pinyin(record: &CERecord) -> &str {
+    lazy_static! {
+        static ref EXTRACT_PINYIN_REGEX: Regex = Regex::new(r"\[(?P<pinyin>.*?)\]").unwrap();
+    }
+
+    EXTRACT_PINYIN_REGEX
+        .captures(&record.line)
+        .and_then(|pr| pr.name("pinyin"))
+        .map(|pr| pr.as_str())
+        .unwrap_or(&record.wade_giles_pinyin)
+}
+
+pub fn get_record_id(record: &CERecord) -> String {
+    get_detail_id(
+        &record.traditional,
+        &record.simplified,
+        get_record_pinyin(record),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::parse_ce_record::parse_ce_record;
+
+    #[test]
+    fn should_be_stable() {
+        assert_eq!(
+            get_detail_id("地窖", "地窖", "di4 jiao4"),
+            get_detail_id("地窖", "地窖", " di4  jiao4 ")
+        );
+        assert_eq!(get_detail_id("地窖", "地窖", "di4 jiao4").len(), 16);
+    }
+
+    #[test]
+    fn should_not_depend_on_line_number() {
+        let line = "地窖 地窖 [di4 jiao4] /cellar/basement/";
+        let first = parse_ce_record(line, 1);
+        let second = parse_ce_record(line, 1000);
+        assert_eq!(get_record_id(&first), get_record_id(&second));
+    }
+
+    #[test]
+    fn should_share_id_of_same_headword_and_pinyin() {
+        let first = parse_ce_record("還 还 [huan2] /to pay back/", 1);
+        let second = parse_ce_record("還 还 [huan2] /to return/", 2);
+        assert_eq!(get_record_id(&first), get_record_id(&second));
+    }
+
+    #[test]
+    fn should_distinguish_proper_nouns() {
+        let proper_noun = parse_ce_record("華 华 [Hua2] /abbr. for China/surname Hua/", 1);
+        let common_noun = parse_ce_record("華 华 [hua2] /magnificent/", 2);
+        assert_ne!(get_record_id(&proper_noun), get_record_id(&common_noun));
+    }
+}
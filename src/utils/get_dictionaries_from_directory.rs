@@ -0,0 +1,83 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::CERecord;
+use crate::utils::parse_ce_record::parse_ce_record;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Reads every CEDICT-format `<language>.u8` file in `directory`, such as
+/// HanDeDict saved as de.u8 or CFDICT as fr.u8, tagging each record with the
+/// language of its glosses.
+pub fn get_dictionaries_from_directory(directory: &Path) -> Result<Vec<CERecord>, Box<dyn Error>> {
+    let mut list: Vec<CERecord> = Vec::new();
+
+    if !directory.exists() {
+        return Ok(list);
+    }
+
+    let mut file_paths: Vec<_> = fs::read_dir(directory)?
+        .map(|pr| pr.map(|pr| pr.path()))
+        .collect::<Result<_, _>>()?;
+    file_paths.retain(|pr| pr.extension().map_or(false, |pr| pr == "u8"));
+    file_paths.sort();
+
+    for file_path in file_paths {
+        let language = file_path
+            .file_stem()
+            .and_then(|pr| pr.to_str())
+            .ok_or("invalid dictionary file name")?
+            .to_lowercase();
+        let lines = BufReader::open(&file_path)?;
+        let mut index = 1;
+
+        for line in lines {
+            let line = line?;
+
+            if line.starts_with("#") || !line.contains('[') || !line.contains('/') {
+                continue;
+            }
+
+            let mut record = parse_ce_record(&line, index);
+            record.language = language.to_owned();
+            list.push(record);
+            index = index + 1;
+        }
+    }
+
+    Ok(list)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn should_tag_records_with_file_language() {
+        let directory = env::temp_dir().join("refined-cedict-json-dictionaries");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("de.u8"),
+            "# HanDeDict\n傳統 传统 [chuan2 tong3] /Tradition (S)/traditionell (Adj)/\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("fr.u8"),
+            "傳統 传统 [chuan2 tong3] /tradition/\n",
+        )
+        .unwrap();
+        fs::write(directory.join("readme.txt"), "not a dictionary\n").unwrap();
+
+        let records = get_dictionaries_from_directory(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].language, "de");
+        assert_eq!(
+            records[0].meanings,
+            vec!["Tradition (S)", "traditionell (Adj)"]
+        );
+        assert_eq!(records[1].language, "fr");
+        assert_eq!(records[1].simplified, "传统");
+    }
+}
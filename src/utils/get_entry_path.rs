@@ -0,0 +1,100 @@
+use crate::utils::get_hash::get_hash;
+use crate::utils::is_cjk::is_cjk;
+
+const MAX_FILE_NAME_LENGTH: usize = 120;
+
+static RESERVED_FILE_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Returns a file name which is valid on every common filesystem. CJK
+/// characters, lowercase ascii letters, digits and `-` are kept, everything
+/// else (including uppercase letters, so that `A菜` and `a菜` do not collide on
+/// case-insensitive filesystems) is written as `_<hex code point>_`.
+pub fn get_safe_file_name(headword: &str) -> String {
+    let mut name = String::with_capacity(headword.len());
+
+    for character in headword.chars() {
+        if is_cjk(&character)
+            || character.is_ascii_lowercase()
+            || character.is_ascii_digit()
+            || character == '-'
+        {
+            name.push(character);
+            continue;
+        }
+
+        name.push_str(&format!("_{:x}_", character as u32));
+    }
+
+    if RESERVED_FILE_NAMES.contains(&name.as_str()) {
+        name.push('_');
+    }
+
+    if name.len() > MAX_FILE_NAME_LENGTH {
+        let mut end = MAX_FILE_NAME_LENGTH - 17;
+
+        while !name.is_char_boundary(end) {
+            end = end - 1;
+        }
+
+        name = format!("{}-{:016x}", &name[..end], get_hash(headword));
+    }
+
+    name
+}
+
+/// Returns the path of an entry relative to the export directory. Entries are
+/// sharded into 256 subdirectories by the hash of the headword.
+pub fn get_entry_path(headword: &str, extension: &str) -> String {
+    let shard = get_hash(headword) & 0xff;
+
+    format!(
+        "{:02x}/{}.{}",
+        shard,
+        get_safe_file_name(headword),
+        extension
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_keep_cjk() {
+        assert_eq!(get_safe_file_name("地窖"), "地窖");
+    }
+
+    #[test]
+    fn should_escape_latin_and_punctuation() {
+        assert_eq!(get_safe_file_name("A菜"), "_41_菜");
+        assert_eq!(get_safe_file_name("T恤"), "_54_恤");
+        assert_eq!(get_safe_file_name("a菜"), "a菜");
+        assert_eq!(get_safe_file_name("哈利·波特"), "哈利_b7_波特");
+        assert_eq!(get_safe_file_name("A/B"), "_41__2f__42_");
+        assert_eq!(get_safe_file_name("_"), "_5f_");
+    }
+
+    #[test]
+    fn should_not_use_reserved_names() {
+        assert_eq!(get_safe_file_name("con"), "con_");
+    }
+
+    #[test]
+    fn should_truncate_long_names() {
+        let headword = "一".repeat(60);
+        let name = get_safe_file_name(&headword);
+        assert!(name.len() <= MAX_FILE_NAME_LENGTH);
+        assert!(name.ends_with(&format!("{:016x}", get_hash(&headword))));
+    }
+
+    #[test]
+    fn should_shard_entry_path() {
+        let path = get_entry_path("地窖", "json");
+        assert_eq!(path, get_entry_path("地窖", "json"));
+        assert_eq!(path.len(), "00/".len() + "地窖.json".len());
+        assert!(path.ends_with("/地窖.json"));
+    }
+}
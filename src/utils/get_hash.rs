@@ -0,0 +1,27 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a hash. Unlike `DefaultHasher` it is guaranteed to give the
+/// same value across Rust releases, so it is safe to persist.
+pub fn get_hash(value: &str) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+
+    for byte in value.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_hash() {
+        assert_eq!(get_hash(""), 0xcbf29ce484222325);
+        assert_eq!(get_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(get_hash("foobar"), 0x85944171f73967e8);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::HskLevel;
+use crate::utils::get_lines_from_file;
+use crate::utils::get_row_from_file;
+use std::error::Error;
+use std::path::Path;
+
+pub fn get_hsk_levels_from_directory(
+    assets_directory: &Path,
+) -> Result<Vec<HskLevel>, Box<dyn Error>> {
+    let mut list: Vec<HskLevel> = Vec::with_capacity(13);
+
+    for level in 1..=6 {
+        let file_name = format!("hsk-version-2-{}.txt", level);
+        let mut words = get_lines_from_file(&assets_directory.join(file_name))?;
+        words.remove("");
+
+        list.push(HskLevel {
+            tag: format!("hsk-2-{}", level),
+            version: 2,
+            level,
+            words,
+        });
+    }
+
+    for level in 1..=7 {
+        let file_name = format!("hsk-version-3-{}.txt", level);
+        let words = get_row_from_file(&assets_directory.join(file_name), 1, "\t")?;
+
+        list.push(HskLevel {
+            tag: format!("hsk-3-{}", level),
+            version: 3,
+            level,
+            words,
+        });
+    }
+
+    Ok(list)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn should_get_hsk_levels() {
+        let current_directory = env::current_dir().unwrap();
+        let assets_directory = current_directory.join("assets");
+        let levels = get_hsk_levels_from_directory(&assets_directory).unwrap();
+
+        assert_eq!(levels.len(), 13);
+        assert_eq!(levels[0].tag, "hsk-2-1");
+        assert!(levels[0].words.contains("爸爸"));
+        assert_eq!(levels[6].tag, "hsk-3-1");
+        assert_eq!(levels[6].version, 3);
+        assert!(levels[6].words.contains("爱好"));
+        assert!(!levels[6].words.contains("愛好"));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::refiner::parse_ids::parse_ids;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Reads IDS data in the tab separated layout of the CHISE and cjkvi-ids files,
+/// "U+5A36<tab>娶<tab>⿱取女[GTJ]", keeping the first sequence that parses.
+/// Region tags and the "^...$(G)" wrapping of alternatives are removed.
+pub fn get_ids_from_file(file_path: &Path) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    lazy_static! {
+        static ref TAG_REGEX: Regex = Regex::new(r"^\^|\$?\([A-Z]*\)$|\[[A-Z]*\]$").unwrap();
+    }
+
+    let lines = BufReader::open(file_path)?;
+    let mut dict: HashMap<String, String> = HashMap::new();
+
+    for line in lines {
+        let line = line?;
+
+        if line.starts_with('#') || line.starts_with(";;") || line.trim().is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.trim_end().split('\t').collect();
+
+        if parts.len() < 3 {
+            return Err("invalid file format".into());
+        }
+
+        let character = parts[1].trim();
+
+        for item in &parts[2..] {
+            let ids = TAG_REGEX.replace_all(item.trim(), "").to_string();
+
+            if ids.is_empty() || ids == character || parse_ids(&ids).is_err() {
+                continue;
+            }
+
+            dict.insert(character.to_owned(), ids);
+            break;
+        }
+    }
+
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_get_ids_from_file() {
+        let file_path = env::temp_dir().join("refined-cedict-json-ids.txt");
+        fs::write(
+            &file_path,
+            "# comment\nU+5973\t女\t女\nU+597D\t好\t⿰女子\nU+5A36\t娶\t^⿱取女$(GTJ)\t⿱⿰耳又女[K]\nU+0000\tx\t⿰x\t⿱{1}x[G]\n",
+        )
+        .unwrap();
+
+        let result = get_ids_from_file(&file_path).unwrap();
+        fs::remove_file(&file_path).unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result["好"], "⿰女子");
+        assert_eq!(result["娶"], "⿱取女");
+        assert_eq!(result["x"], "⿱{1}x");
+    }
+}
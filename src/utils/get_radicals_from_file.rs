@@ -1,17 +1,24 @@
 use crate::customReader::custom_reader::BufReader;
 use crate::models::Radical;
+use crate::utils::kangxi_radicals::get_kangxi_radical_map;
 use std::collections::HashMap;
 use std::error::Error;
 use std::path::Path;
 
 pub fn get_radicals_from_file(
     file_path: &Path,
-) -> Result<HashMap<String, Radical>, Box<dyn Error>> {
+) -> Result<HashMap<String, Vec<Radical>>, Box<dyn Error>> {
     let reader = BufReader::open(file_path)?;
-    let mut dict: HashMap<String, Radical> = HashMap::with_capacity(300);
+    let mut dict: HashMap<String, Vec<Radical>> = HashMap::with_capacity(300);
 
     for line in reader {
         let line = line?;
+        let line = line.trim_end();
+
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
         let parts: Vec<&str> = line.split(";").collect();
 
         if parts.len() < 4 {
@@ -30,12 +37,27 @@ pub fn get_radicals_from_file(
             stroke_count,
         };
 
-        dict.insert(parts[0].to_owned(), record);
+        dict.entry(parts[0].to_owned())
+            .or_insert_with(Vec::new)
+            .push(record);
     }
 
     Ok(dict)
 }
 
+/// assets/radicals.txt when present, the built-in Kangxi table otherwise.
+pub fn get_radicals(
+    assets_directory: &Path,
+) -> Result<HashMap<String, Vec<Radical>>, Box<dyn Error>> {
+    let file_path = assets_directory.join("radicals.txt");
+
+    if file_path.exists() {
+        get_radicals_from_file(&file_path)
+    } else {
+        Ok(get_kangxi_radical_map())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::env;
@@ -43,11 +65,11 @@ mod test {
     use super::*;
 
     #[test]
-    fn should_get_radicals_from_wikipedia() {
+    fn should_get_radicals_from_file() {
         let current_directory = env::current_dir().unwrap();
         let assets_directory = current_directory.join("assets");
 
         let radicals = get_radicals_from_file(&assets_directory.join("radicals.txt")).unwrap();
-        assert_eq!(radicals.len(), 282);
+        assert_eq!(radicals, get_kangxi_radical_map());
     }
 }
@@ -0,0 +1,85 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::Stroke;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct GraphicsRecord {
+    character: String,
+    strokes: Vec<String>,
+    medians: Vec<Vec<[i32; 2]>>,
+}
+
+/// Reads a Make Me a Hanzi style graphics.txt, one JSON object per line with
+/// the outline of every stroke and its median in writing order.
+pub fn get_stroke_graphics_from_file(
+    file_path: &Path,
+) -> Result<HashMap<String, Vec<Stroke>>, Box<dyn Error>> {
+    let lines = BufReader::open(file_path)?;
+    let mut dict: HashMap<String, Vec<Stroke>> = HashMap::new();
+
+    for line in lines {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: GraphicsRecord = serde_json::from_str(&line)?;
+
+        if record.strokes.len() != record.medians.len() {
+            return Err(format!("stroke and median counts differ for {}", record.character).into());
+        }
+
+        let strokes = record
+            .strokes
+            .into_iter()
+            .zip(record.medians)
+            .map(|(path, median)| Stroke { path, median })
+            .collect();
+
+        dict.insert(record.character, strokes);
+    }
+
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_get_stroke_graphics_from_file() {
+        let file_path = env::temp_dir().join("refined-cedict-json-graphics.txt");
+        fs::write(
+            &file_path,
+            "{\"character\":\"一\",\"strokes\":[\"M 518 382 Q 572 385 623 389 Z\"],\"medians\":[[[27,393],[960,389]]]}\n\n{\"character\":\"二\",\"strokes\":[\"M 1 2 Z\"],\"medians\":[]}\n",
+        )
+        .unwrap();
+
+        let result = get_stroke_graphics_from_file(&file_path);
+        fs::remove_file(&file_path).unwrap();
+        assert!(result.is_err());
+
+        fs::write(
+            &file_path,
+            "{\"character\":\"一\",\"strokes\":[\"M 518 382 Q 572 385 623 389 Z\"],\"medians\":[[[27,393],[960,389]]]}\n",
+        )
+        .unwrap();
+
+        let result = get_stroke_graphics_from_file(&file_path).unwrap();
+        fs::remove_file(&file_path).unwrap();
+
+        assert_eq!(
+            result["一"],
+            vec![Stroke {
+                path: "M 518 382 Q 572 385 623 389 Z".to_string(),
+                median: vec![[27, 393], [960, 389]],
+            }]
+        );
+    }
+}
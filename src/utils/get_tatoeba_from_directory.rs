@@ -0,0 +1,111 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::Example;
+use crate::models::Translation;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Tatoeba uses ISO 639-3 codes; translations are kept in the languages the
+/// dictionaries have glosses for, under their ISO 639-1 codes.
+fn get_translation_language(code: &str) -> Option<&'static str> {
+    match code {
+        "eng" => Some("en"),
+        "deu" => Some("de"),
+        "fra" => Some("fr"),
+        _ => None,
+    }
+}
+
+/// Mandarin sentences from the `sentences.csv` and `links.csv` exports in
+/// `directory`, leaving out the ones nobody has translated.
+pub fn get_tatoeba_from_directory(directory: &Path) -> Result<Vec<Example>, Box<dyn Error>> {
+    let mut sentences: Vec<Example> = Vec::new();
+    let mut translations: HashMap<u32, Translation> = HashMap::new();
+
+    for line in BufReader::open(directory.join("sentences.csv"))? {
+        let line = line?;
+        let parts: Vec<&str> = line
+            .trim_end_matches(&['\r', '\n'][..])
+            .split('\t')
+            .collect();
+
+        if parts.len() < 3 {
+            continue;
+        }
+
+        let id = parts[0].parse::<u32>()?;
+        let value = parts[2].trim().to_string();
+
+        if parts[1] == "cmn" {
+            sentences.push(Example {
+                id,
+                value,
+                translations: Vec::new(),
+            });
+        } else if let Some(language) = get_translation_language(parts[1]) {
+            let language = language.to_string();
+            translations.insert(id, Translation { language, value });
+        }
+    }
+
+    let index: HashMap<u32, usize> = sentences
+        .iter()
+        .enumerate()
+        .map(|(position, sentence)| (sentence.id, position))
+        .collect();
+
+    for line in BufReader::open(directory.join("links.csv"))? {
+        let line = line?;
+        let mut parts = line.split('\t').map(|pr| pr.trim());
+
+        let (sentence_id, translation_id) = match (parts.next(), parts.next()) {
+            (Some(sentence_id), Some(translation_id)) => (sentence_id, translation_id),
+            _ => continue,
+        };
+
+        let position = match index.get(&sentence_id.parse::<u32>()?) {
+            Some(position) => *position,
+            None => continue,
+        };
+
+        if let Some(translation) = translations.get(&translation_id.parse::<u32>()?) {
+            sentences[position].translations.push(translation.clone());
+        }
+    }
+
+    sentences.retain(|pr| !pr.translations.is_empty());
+
+    Ok(sentences)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_get_translated_mandarin_sentences() {
+        let directory = env::temp_dir().join("refined-cedict-json-tatoeba");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("sentences.csv"),
+            "1\tcmn\t我們試試看！\n2\tcmn\t我喜欢你。\n3\teng\tLet's try something.\n4\tfra\tEssayons quelque chose !\n5\tjpn\t何かしてみましょう。\n",
+        )
+        .unwrap();
+        fs::write(directory.join("links.csv"), "1\t3\n1\t4\n1\t5\n3\t1\n").unwrap();
+
+        let sentences = get_tatoeba_from_directory(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(sentences.len(), 1);
+        assert_eq!(sentences[0].id, 1);
+        assert_eq!(sentences[0].value, "我們試試看！");
+        assert_eq!(sentences[0].translations.len(), 2);
+        assert_eq!(sentences[0].translations[0].language, "en");
+        assert_eq!(
+            sentences[0].translations[1].value,
+            "Essayons quelque chose !"
+        );
+    }
+}
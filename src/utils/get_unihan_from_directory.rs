@@ -0,0 +1,182 @@
+use crate::customReader::custom_reader::BufReader;
+use crate::models::RadicalStroke;
+use crate::models::UnihanRecord;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::path::Path;
+
+/// kTotalStrokes and kRSUnicode moved to Unihan_IRGSources.txt in Unicode 13,
+/// the older files are read too so that either release works.
+static UNIHAN_FILES: &[&str] = &[
+    "Unihan_Readings.txt",
+    "Unihan_IRGSources.txt",
+    "Unihan_RadicalStrokeCounts.txt",
+    "Unihan_DictionaryLikeData.txt",
+    "Unihan_Variants.txt",
+];
+
+/// "U+5A36" or "U+5A36<kMatthews" to "娶".
+fn parse_code_point(value: &str) -> Result<char, Box<dyn Error>> {
+    let value = value.split('<').next().unwrap_or_default();
+    let hex = value
+        .strip_prefix("U+")
+        .ok_or_else(|| format!("invalid code point {}", value))?;
+    let code_point = u32::from_str_radix(hex, 16)?;
+
+    Ok(char::try_from(code_point)?)
+}
+
+fn parse_radical_stroke(value: &str) -> Result<RadicalStroke, Box<dyn Error>> {
+    let mut parts = value.splitn(2, '.');
+    let radical = parts.next().unwrap_or_default();
+    let additional_strokes = parts
+        .next()
+        .ok_or_else(|| format!("invalid radical stroke {}", value))?;
+
+    Ok(RadicalStroke {
+        radical: radical.trim_end_matches('\'').parse::<u8>()?,
+        simplified: radical.ends_with('\''),
+        additional_strokes: additional_strokes.parse::<i8>()?,
+    })
+}
+
+fn apply_field(record: &mut UnihanRecord, field: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let first = value.split_whitespace().next().unwrap_or_default();
+
+    match field {
+        "kTotalStrokes" => record.total_strokes = Some(first.parse::<u8>()?),
+        "kRSUnicode" => record.radical_stroke = Some(parse_radical_stroke(first)?),
+        "kMandarin" => record.mandarin = value.split_whitespace().map(String::from).collect(),
+        "kDefinition" => record.definition = Some(value.to_owned()),
+        "kSimplifiedVariant" | "kTraditionalVariant" => {
+            let variants = value
+                .split_whitespace()
+                .map(|pr| parse_code_point(pr).map(|pr| pr.to_string()))
+                .collect::<Result<Vec<String>, Box<dyn Error>>>()?;
+
+            if field == "kSimplifiedVariant" {
+                record.simplified_variants = variants;
+            } else {
+                record.traditional_variants = variants;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Reads the Unihan text files found in `directory`, keeping the characters in
+/// `characters`, or every character when it is empty.
+pub fn get_unihan_from_directory(
+    directory: &Path,
+    characters: &HashSet<char>,
+) -> Result<HashMap<String, UnihanRecord>, Box<dyn Error>> {
+    let mut dict: HashMap<String, UnihanRecord> = HashMap::new();
+    let mut found = false;
+
+    for file_name in UNIHAN_FILES {
+        let file_path = directory.join(file_name);
+
+        if !file_path.exists() {
+            continue;
+        }
+
+        found = true;
+
+        for line in BufReader::open(&file_path)? {
+            let line = line?;
+            let line = line.trim_end();
+
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.splitn(3, '\t').collect();
+
+            if parts.len() < 3 {
+                return Err("invalid file format".into());
+            }
+
+            let character = parse_code_point(parts[0])?;
+
+            if !characters.is_empty() && !characters.contains(&character) {
+                continue;
+            }
+
+            let record = dict
+                .entry(character.to_string())
+                .or_insert_with(UnihanRecord::default);
+
+            apply_field(record, parts[1], parts[2])
+                .map_err(|error| format!("{} in {:?}: {}", line, file_name, error))?;
+        }
+    }
+
+    if !found {
+        return Err(format!("no Unihan files found in {:?}", directory).into());
+    }
+
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_get_unihan_from_directory() {
+        let directory = env::temp_dir().join("refined-cedict-json-unihan");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("Unihan_Readings.txt"),
+            "# Unihan_Readings.txt\nU+4E07\tkDefinition\tten thousand; innumerable\nU+4E07\tkMandarin\twàn mò\nU+842C\tkMandarin\twàn\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("Unihan_IRGSources.txt"),
+            "U+4E07\tkRSUnicode\t1.2\nU+4E07\tkTotalStrokes\t3\nU+8BF4\tkRSUnicode\t149'.7\nU+842C\tkTotalStrokes\t12 13\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("Unihan_Variants.txt"),
+            "U+4E07\tkTraditionalVariant\tU+842C\nU+842C\tkSimplifiedVariant\tU+4E07<kMatthews\n",
+        )
+        .unwrap();
+
+        let characters: HashSet<char> = "万萬".chars().collect();
+        let result = get_unihan_from_directory(&directory, &characters).unwrap();
+        let all = get_unihan_from_directory(&directory, &HashSet::new()).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result["万"],
+            UnihanRecord {
+                total_strokes: Some(3),
+                radical_stroke: Some(RadicalStroke {
+                    radical: 1,
+                    simplified: false,
+                    additional_strokes: 2
+                }),
+                mandarin: vec!["wàn".to_string(), "mò".to_string()],
+                definition: Some("ten thousand; innumerable".to_string()),
+                simplified_variants: Vec::new(),
+                traditional_variants: vec!["萬".to_string()],
+            }
+        );
+        assert_eq!(result["萬"].total_strokes, Some(12));
+        assert_eq!(result["萬"].simplified_variants, vec!["万"]);
+        assert!(all["说"].radical_stroke.as_ref().unwrap().simplified);
+    }
+
+    #[test]
+    fn should_reject_missing_directory() {
+        let directory = env::temp_dir().join("refined-cedict-json-unihan-missing");
+        assert!(get_unihan_from_directory(&directory, &HashSet::new()).is_err());
+    }
+}
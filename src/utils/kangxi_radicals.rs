@@ -0,0 +1,404 @@
+use crate::models::Radical;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::LineWriter;
+use std::path::Path;
+
+/// Bumped whenever an entry of the table changes, and written into the
+/// radicals.txt export so that stale copies can be told apart.
+pub static KANGXI_RADICALS_VERSION: u32 = 2;
+
+static KANGXI_RADICALS_BLOCK: u32 = 0x2F00;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KangxiRadical {
+    pub number: u8,
+    pub value: &'static str,
+    pub stroke_count: u8,
+    pub meaning: &'static str,
+    pub pinyin: &'static str,
+    /// Variant forms such as 氵 for 水, with their own stroke counts.
+    pub variants: &'static [(&'static str, u8)],
+}
+
+impl KangxiRadical {
+    /// The character in the Kangxi Radicals block, ⼈ (U+2F08) for 人.
+    pub fn get_code_point(&self) -> char {
+        char::try_from(KANGXI_RADICALS_BLOCK + self.number as u32 - 1).unwrap()
+    }
+}
+
+/// Value, stroke count, meaning, pinyin and variant forms, in radical number
+/// order. Values are the unified ideographs the Kangxi Radicals block maps to.
+#[rustfmt::skip]
+static TABLE: [(&str, u8, &str, &str, &[(&str, u8)]); 214] = [
+    ("一", 1, "one", "yī", &[]),
+    ("丨", 1, "line", "gǔn", &[]),
+    ("丶", 1, "dot", "zhǔ", &[]),
+    ("丿", 1, "slash", "piě", &[]),
+    ("乙", 1, "second", "yǐ", &[("乚", 1)]),
+    ("亅", 1, "hook", "jué", &[]),
+    ("二", 2, "two", "èr", &[]),
+    ("亠", 2, "lid", "tóu", &[]),
+    ("人", 2, "man", "rén", &[("亻", 2)]),
+    ("儿", 2, "son, legs", "ér", &[]),
+    ("入", 2, "enter", "rù", &[]),
+    ("八", 2, "eight", "bā", &[("丷", 2)]),
+    ("冂", 2, "wide", "jiōng", &[]),
+    ("冖", 2, "cloth cover", "mì", &[]),
+    ("冫", 2, "ice", "bīng", &[]),
+    ("几", 2, "table", "jī", &[]),
+    ("凵", 2, "receptacle", "kǎn", &[]),
+    ("刀", 2, "knife", "dāo", &[("刂", 2)]),
+    ("力", 2, "power", "lì", &[]),
+    ("勹", 2, "wrap", "bāo", &[]),
+    ("匕", 2, "spoon", "bǐ", &[]),
+    ("匚", 2, "box", "fāng", &[]),
+    ("匸", 2, "hiding enclosure", "xì", &[]),
+    ("十", 2, "ten", "shí", &[]),
+    ("卜", 2, "divination", "bǔ", &[]),
+    ("卩", 2, "seal", "jié", &[("㔾", 2)]),
+    ("厂", 2, "cliff", "hǎn", &[]),
+    ("厶", 2, "private", "sī", &[]),
+    ("又", 2, "again", "yòu", &[]),
+    ("口", 3, "mouth", "kǒu", &[]),
+    ("囗", 3, "enclosure", "wéi", &[]),
+    ("土", 3, "earth", "tǔ", &[]),
+    ("士", 3, "scholar", "shì", &[]),
+    ("夂", 3, "go", "zhǐ", &[]),
+    ("夊", 3, "go slowly", "suī", &[]),
+    ("夕", 3, "evening", "xī", &[]),
+    ("大", 3, "big", "dà", &[]),
+    ("女", 3, "woman", "nǚ", &[]),
+    ("子", 3, "child", "zǐ", &[]),
+    ("宀", 3, "roof", "mián", &[]),
+    ("寸", 3, "inch", "cùn", &[]),
+    ("小", 3, "small", "xiǎo", &[]),
+    ("尢", 3, "lame", "wāng", &[]),
+    ("尸", 3, "corpse", "shī", &[]),
+    ("屮", 3, "sprout", "chè", &[]),
+    ("山", 3, "mountain", "shān", &[]),
+    ("巛", 3, "river", "chuān", &[("川", 3), ("巜", 2)]),
+    ("工", 3, "work", "gōng", &[]),
+    ("己", 3, "oneself", "jǐ", &[]),
+    ("巾", 3, "turban", "jīn", &[]),
+    ("干", 3, "dry", "gān", &[]),
+    ("幺", 3, "short thread", "yāo", &[]),
+    ("广", 3, "dotted cliff", "guǎng", &[]),
+    ("廴", 3, "long stride", "yǐn", &[]),
+    ("廾", 3, "two hands", "gǒng", &[]),
+    ("弋", 3, "shoot", "yì", &[]),
+    ("弓", 3, "bow", "gōng", &[]),
+    ("彐", 3, "snout", "jì", &[("彑", 3)]),
+    ("彡", 3, "bristle", "shān", &[]),
+    ("彳", 3, "step", "chì", &[]),
+    ("心", 4, "heart", "xīn", &[("忄", 3)]),
+    ("戈", 4, "halberd", "gē", &[]),
+    ("戶", 4, "door", "hù", &[("户", 4), ("戸", 4)]),
+    ("手", 4, "hand", "shǒu", &[("扌", 3)]),
+    ("支", 4, "branch", "zhī", &[]),
+    ("攴", 4, "rap", "pū", &[("攵", 4)]),
+    ("文", 4, "script", "wén", &[]),
+    ("斗", 4, "dipper", "dǒu", &[]),
+    ("斤", 4, "axe", "jīn", &[]),
+    ("方", 4, "square", "fāng", &[]),
+    ("无", 4, "not", "wú", &[("旡", 4)]),
+    ("日", 4, "sun", "rì", &[]),
+    ("曰", 4, "say", "yuē", &[]),
+    ("月", 4, "moon", "yuè", &[]),
+    ("木", 4, "tree", "mù", &[]),
+    ("欠", 4, "lack", "qiàn", &[]),
+    ("止", 4, "stop", "zhǐ", &[]),
+    ("歹", 4, "death", "dǎi", &[("歺", 5)]),
+    ("殳", 4, "weapon", "shū", &[]),
+    ("毋", 4, "do not", "wú", &[("母", 5)]),
+    ("比", 4, "compare", "bǐ", &[]),
+    ("毛", 4, "fur", "máo", &[]),
+    ("氏", 4, "clan", "shì", &[]),
+    ("气", 4, "steam", "qì", &[]),
+    ("水", 4, "water", "shuǐ", &[("氵", 3), ("氺", 5)]),
+    ("火", 4, "fire", "huǒ", &[("灬", 4)]),
+    ("爪", 4, "claw", "zhǎo", &[("爫", 4)]),
+    ("父", 4, "father", "fù", &[]),
+    ("爻", 4, "double x", "yáo", &[]),
+    ("爿", 4, "half tree trunk", "qiáng", &[("丬", 3)]),
+    ("片", 4, "slice", "piàn", &[]),
+    ("牙", 4, "fang", "yá", &[]),
+    ("牛", 4, "cow", "niú", &[("牜", 4)]),
+    ("犬", 4, "dog", "quǎn", &[("犭", 3)]),
+    ("玄", 5, "profound", "xuán", &[]),
+    ("玉", 5, "jade", "yù", &[("王", 4)]),
+    ("瓜", 5, "melon", "guā", &[]),
+    ("瓦", 5, "tile", "wǎ", &[]),
+    ("甘", 5, "sweet", "gān", &[]),
+    ("生", 5, "life", "shēng", &[]),
+    ("用", 5, "use", "yòng", &[]),
+    ("田", 5, "field", "tián", &[]),
+    ("疋", 5, "bolt of cloth", "pǐ", &[]),
+    ("疒", 5, "sickness", "nè", &[]),
+    ("癶", 5, "footsteps", "bō", &[]),
+    ("白", 5, "white", "bái", &[]),
+    ("皮", 5, "skin", "pí", &[]),
+    ("皿", 5, "dish", "mǐn", &[]),
+    ("目", 5, "eye", "mù", &[]),
+    ("矛", 5, "spear", "máo", &[]),
+    ("矢", 5, "arrow", "shǐ", &[]),
+    ("石", 5, "stone", "shí", &[]),
+    ("示", 5, "spirit", "shì", &[("礻", 4)]),
+    ("禸", 5, "track", "róu", &[]),
+    ("禾", 5, "grain", "hé", &[]),
+    ("穴", 5, "cave", "xué", &[]),
+    ("立", 5, "stand", "lì", &[]),
+    ("竹", 6, "bamboo", "zhú", &[]),
+    ("米", 6, "rice", "mǐ", &[]),
+    ("糸", 6, "silk", "mì", &[("糹", 6), ("纟", 3)]),
+    ("缶", 6, "jar", "fǒu", &[]),
+    ("网", 6, "net", "wǎng", &[("罒", 5), ("罓", 4)]),
+    ("羊", 6, "sheep", "yáng", &[]),
+    ("羽", 6, "feather", "yǔ", &[]),
+    ("老", 6, "old", "lǎo", &[("耂", 4)]),
+    ("而", 6, "and", "ér", &[]),
+    ("耒", 6, "plow", "lěi", &[]),
+    ("耳", 6, "ear", "ěr", &[]),
+    ("聿", 6, "brush", "yù", &[]),
+    ("肉", 6, "meat", "ròu", &[("月", 4)]),
+    ("臣", 6, "minister", "chén", &[]),
+    ("自", 6, "self", "zì", &[]),
+    ("至", 6, "arrive", "zhì", &[]),
+    ("臼", 6, "mortar", "jiù", &[]),
+    ("舌", 6, "tongue", "shé", &[]),
+    ("舛", 6, "oppose", "chuǎn", &[]),
+    ("舟", 6, "boat", "zhōu", &[]),
+    ("艮", 6, "stopping", "gèn", &[]),
+    ("色", 6, "colour", "sè", &[]),
+    ("艸", 6, "grass", "cǎo", &[("艹", 3)]),
+    ("虍", 6, "tiger", "hū", &[]),
+    ("虫", 6, "insect", "chóng", &[]),
+    ("血", 6, "blood", "xuè", &[]),
+    ("行", 6, "walk enclosure", "xíng", &[]),
+    ("衣", 6, "clothes", "yī", &[("衤", 5)]),
+    ("襾", 6, "west", "yà", &[("西", 6), ("覀", 6)]),
+    ("見", 7, "see", "jiàn", &[("见", 4)]),
+    ("角", 7, "horn", "jiǎo", &[]),
+    ("言", 7, "speech", "yán", &[("訁", 7), ("讠", 2)]),
+    ("谷", 7, "valley", "gǔ", &[]),
+    ("豆", 7, "bean", "dòu", &[]),
+    ("豕", 7, "pig", "shǐ", &[]),
+    ("豸", 7, "badger", "zhì", &[]),
+    ("貝", 7, "shell", "bèi", &[("贝", 4)]),
+    ("赤", 7, "red", "chì", &[]),
+    ("走", 7, "run", "zǒu", &[]),
+    ("足", 7, "foot", "zú", &[("𧾷", 7)]),
+    ("身", 7, "body", "shēn", &[]),
+    ("車", 7, "cart", "chē", &[("车", 4)]),
+    ("辛", 7, "bitter", "xīn", &[]),
+    ("辰", 7, "morning", "chén", &[]),
+    ("辵", 7, "walk", "chuò", &[("辶", 3)]),
+    ("邑", 7, "city", "yì", &[("阝", 2)]),
+    ("酉", 7, "wine", "yǒu", &[]),
+    ("釆", 7, "distinguish", "biàn", &[]),
+    ("里", 7, "village", "lǐ", &[]),
+    ("金", 8, "gold", "jīn", &[("釒", 8), ("钅", 5)]),
+    ("長", 8, "long", "cháng", &[("镸", 8), ("长", 4)]),
+    ("門", 8, "gate", "mén", &[("门", 3)]),
+    ("阜", 8, "mound", "fù", &[("阝", 2)]),
+    ("隶", 8, "slave", "lì", &[]),
+    ("隹", 8, "short-tailed bird", "zhuī", &[]),
+    ("雨", 8, "rain", "yǔ", &[]),
+    ("靑", 8, "blue", "qīng", &[("青", 8)]),
+    ("非", 8, "wrong", "fēi", &[]),
+    ("面", 9, "face", "miàn", &[("靣", 9)]),
+    ("革", 9, "leather", "gé", &[]),
+    ("韋", 9, "tanned leather", "wéi", &[("韦", 4)]),
+    ("韭", 9, "leek", "jiǔ", &[]),
+    ("音", 9, "sound", "yīn", &[]),
+    ("頁", 9, "leaf", "yè", &[("页", 6)]),
+    ("風", 9, "wind", "fēng", &[("风", 4)]),
+    ("飛", 9, "fly", "fēi", &[("飞", 3)]),
+    ("食", 9, "eat", "shí", &[("飠", 8), ("饣", 3)]),
+    ("首", 9, "head", "shǒu", &[]),
+    ("香", 9, "fragrant", "xiāng", &[]),
+    ("馬", 10, "horse", "mǎ", &[("马", 3)]),
+    ("骨", 10, "bone", "gǔ", &[]),
+    ("高", 10, "tall", "gāo", &[("髙", 10)]),
+    ("髟", 10, "hair", "biāo", &[]),
+    ("鬥", 10, "fight", "dòu", &[]),
+    ("鬯", 10, "sacrificial wine", "chàng", &[]),
+    ("鬲", 10, "cauldron", "lì", &[]),
+    ("鬼", 10, "ghost", "guǐ", &[]),
+    ("魚", 11, "fish", "yú", &[("鱼", 8)]),
+    ("鳥", 11, "bird", "niǎo", &[("鸟", 5)]),
+    ("鹵", 11, "salt", "lǔ", &[("卤", 7)]),
+    ("鹿", 11, "deer", "lù", &[]),
+    ("麥", 11, "wheat", "mài", &[("麦", 7)]),
+    ("麻", 11, "hemp", "má", &[]),
+    ("黃", 12, "yellow", "huáng", &[("黄", 11)]),
+    ("黍", 12, "millet", "shǔ", &[]),
+    ("黑", 12, "black", "hēi", &[("黒", 11)]),
+    ("黹", 12, "embroidery", "zhǐ", &[]),
+    ("黽", 13, "frog", "mǐn", &[("黾", 8)]),
+    ("鼎", 13, "tripod", "dǐng", &[]),
+    ("鼓", 13, "drum", "gǔ", &[]),
+    ("鼠", 13, "rat", "shǔ", &[]),
+    ("鼻", 14, "nose", "bí", &[]),
+    ("齊", 14, "even", "qí", &[("齐", 6)]),
+    ("齒", 15, "tooth", "chǐ", &[("齿", 8)]),
+    ("龍", 16, "dragon", "lóng", &[("龙", 5)]),
+    ("龜", 16, "turtle", "guī", &[("龟", 7)]),
+    ("龠", 17, "flute", "yuè", &[]),
+];
+
+pub fn get_kangxi_radicals() -> Vec<KangxiRadical> {
+    TABLE
+        .iter()
+        .enumerate()
+        .map(|(index, item)| KangxiRadical {
+            number: index as u8 + 1,
+            value: item.0,
+            stroke_count: item.1,
+            meaning: item.2,
+            pinyin: item.3,
+            variants: item.4,
+        })
+        .collect()
+}
+
+/// Every radical and variant form with the Kangxi Radicals block character of
+/// the radical it belongs to. A form shared by several radicals, like 阝 for 邑
+/// and 阜 or 月 for 月 and 肉, is listed once per radical, radicals first.
+fn get_forms() -> Vec<(Radical, char)> {
+    let radicals = get_kangxi_radicals();
+    let mut list: Vec<(Radical, char)> = Vec::with_capacity(300);
+
+    for radical in &radicals {
+        let record = Radical {
+            value: radical.value.to_string(),
+            stroke_count: radical.stroke_count,
+            meaning: radical.meaning.to_string(),
+            pinyin: radical.pinyin.to_string(),
+        };
+
+        list.push((record, radical.get_code_point()));
+    }
+
+    for radical in &radicals {
+        for (value, stroke_count) in radical.variants {
+            let record = Radical {
+                value: value.to_string(),
+                stroke_count: *stroke_count,
+                meaning: radical.meaning.to_string(),
+                pinyin: radical.pinyin.to_string(),
+            };
+
+            list.push((record, radical.get_code_point()));
+        }
+    }
+
+    list
+}
+
+pub fn get_kangxi_radical_records() -> Vec<Radical> {
+    get_forms().into_iter().map(|pr| pr.0).collect()
+}
+
+/// Radicals by form, with every radical a shared form belongs to.
+pub fn get_kangxi_radical_map() -> HashMap<String, Vec<Radical>> {
+    let mut map: HashMap<String, Vec<Radical>> = HashMap::with_capacity(300);
+
+    for record in get_kangxi_radical_records() {
+        map.entry(record.value.to_owned())
+            .or_insert_with(Vec::new)
+            .push(record);
+    }
+
+    map
+}
+
+/// Writes the records in the "value;meaning;stroke count;pinyin" layout that
+/// `get_radicals_from_file` reads, followed by the radical's code point, after
+/// a comment naming the table version.
+pub fn save_kangxi_radicals_to_file(file_path: &Path) -> Result<(), Box<dyn Error>> {
+    let file = File::create(file_path)?;
+    let mut line_writer = LineWriter::new(file);
+
+    line_writer.write_all(
+        format!(
+            "# Kangxi radicals, table version {}\n",
+            KANGXI_RADICALS_VERSION
+        )
+        .as_bytes(),
+    )?;
+
+    for (radical, code_point) in get_forms() {
+        let line = format!(
+            "{};{};{};{};{}\n",
+            radical.value, radical.meaning, radical.stroke_count, radical.pinyin, code_point
+        );
+        line_writer.write_all(line.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::get_radicals_from_file::get_radicals_from_file;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn should_list_radicals_in_stroke_order() {
+        let radicals = get_kangxi_radicals();
+
+        assert_eq!(radicals.len(), 214);
+        assert!(radicals
+            .windows(2)
+            .all(|pr| pr[0].stroke_count <= pr[1].stroke_count));
+        assert_eq!(radicals[0].get_code_point(), '\u{2F00}');
+        assert_eq!(radicals[84].value, "水");
+        assert_eq!(radicals[84].get_code_point(), '\u{2F54}');
+        assert_eq!(radicals[213].get_code_point(), '\u{2FD5}');
+    }
+
+    #[test]
+    fn should_get_radical_records_with_variants() {
+        let records = get_kangxi_radical_map();
+
+        assert_eq!(records["氵"][0].stroke_count, 3);
+        assert_eq!(records["氵"][0].meaning, "water");
+        assert_eq!(
+            records.values().map(|pr| pr.len()).sum::<usize>(),
+            get_kangxi_radical_records().len()
+        );
+    }
+
+    #[test]
+    fn should_keep_every_radical_of_shared_forms() {
+        let records = get_kangxi_radical_map();
+        let get_meanings = |value: &str| -> Vec<String> {
+            records[value]
+                .iter()
+                .map(|pr| pr.meaning.to_owned())
+                .collect()
+        };
+
+        assert_eq!(get_meanings("阝"), vec!["city", "mound"]);
+        assert_eq!(get_meanings("月"), vec!["moon", "meat"]);
+        assert_eq!(records["月"][1].stroke_count, 4);
+    }
+
+    #[test]
+    fn should_save_radicals_readable_by_get_radicals_from_file() {
+        let file_path = env::temp_dir().join("refined-cedict-json-radicals.txt");
+        save_kangxi_radicals_to_file(&file_path).unwrap();
+
+        let result = get_radicals_from_file(&file_path).unwrap();
+        fs::remove_file(&file_path).unwrap();
+
+        assert_eq!(result, get_kangxi_radical_map());
+    }
+}
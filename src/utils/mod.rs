@@ -1,11 +1,12 @@
 use crate::api::get_info_from_writtenchinese;
-use crate::api::get_stroke_count_from_wiktionary;
+use crate::api::stroke_count_resolver::StrokeCountResolver;
 use crate::customReader::custom_reader::BufReader;
 use crate::models::Decomposition;
 use crate::models::Descriptor;
 use crate::models::Radical;
 use crate::CERecord;
 use crate::PinyinMap;
+use checkpoint::Checkpoint;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -19,11 +20,25 @@ use std::io::BufWriter;
 use std::io::LineWriter;
 use std::path::Path;
 use std::path::PathBuf;
+pub mod checkpoint;
+pub mod escape_html;
 pub mod get_abbreviations_from_file;
+pub mod get_cantonese_from_directory;
+pub mod get_component_table_from_file;
 pub mod get_decomposition_from_file;
 pub mod get_descriptors_from_file;
+pub mod get_detail_id;
+pub mod get_dictionaries_from_directory;
+pub mod get_entry_path;
+pub mod get_hash;
+pub mod get_hsk_levels_from_directory;
+pub mod get_ids_from_file;
 pub mod get_radicals_from_file;
+pub mod get_stroke_graphics_from_file;
+pub mod get_tatoeba_from_directory;
+pub mod get_unihan_from_directory;
 pub mod is_cjk;
+pub mod kangxi_radicals;
 pub mod parse_ce_record;
 use crate::utils::is_cjk::is_cjk;
 use crate::utils::parse_ce_record::parse_ce_record;
@@ -169,9 +184,17 @@ pub fn write_lines_to_file(file_path: PathBuf, items: HashSet<char>) -> Result<(
     Ok(())
 }
 
-pub fn import_stroke_order(file_path: &Path) -> Result<Vec<String>, Box<dyn Error>> {
-    let reader = BufReader::open(file_path.clone())?;
-    let mut list = Vec::with_capacity(10000);
+/// Characters no source could resolve are written to `miss_writer`, and
+/// their count returned, so they don't silently drop out of the output.
+pub fn import_stroke_order(
+    file_path: &Path,
+    resolver: &mut StrokeCountResolver,
+    checkpoint: &mut Checkpoint,
+    conflict_writer: &mut dyn Write,
+    miss_writer: &mut dyn Write,
+) -> Result<usize, Box<dyn Error>> {
+    let reader = BufReader::open(file_path)?;
+    let mut miss_count = 0;
 
     for line in reader {
         let line = line?;
@@ -179,19 +202,37 @@ pub fn import_stroke_order(file_path: &Path) -> Result<Vec<String>, Box<dyn Erro
         let mut parts = line.split(" ").map(|s| s.trim());
         let key = parts.next().ok_or("")?;
 
-        let stroke_count = get_stroke_count_from_wiktionary(key)?;
-
-        if stroke_count.is_none() {
-            println!("Could not process character: {}", line);
+        if key.is_empty() || checkpoint.contains(key) {
             continue;
         }
 
-        let line = format!("{} {}\n", key, stroke_count.unwrap());
-        print!("Processing {}", line);
-        list.push(line);
+        let resolution = match resolver.resolve(key) {
+            Ok(resolution) => resolution,
+            Err(error) => {
+                checkpoint.add_retry(key, error.as_ref())?;
+                continue;
+            }
+        };
+
+        if let Some(conflict) = resolution.conflict {
+            conflict_writer.write_all(format!("{}\n", conflict.to_line()).as_bytes())?;
+        }
+
+        match resolution.resolved {
+            Some(resolved) => {
+                let line = format!("{} {}", key, resolved.stroke_count);
+                println!("Processing {} ({})", line, resolved.source);
+                checkpoint.write_line(&line)?;
+            }
+            None => {
+                warn!("Could not resolve the stroke count of {}", key);
+                miss_writer.write_all(format!("{}\n", key).as_bytes())?;
+                miss_count = miss_count + 1;
+            }
+        }
     }
 
-    Ok(list)
+    Ok(miss_count)
 }
 
 pub fn get_single_characters(records: &[CERecord]) -> HashSet<char> {
@@ -367,3 +408,55 @@ pub fn remove_duplicates(file_path: &str) -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::providers::StrokeCountProvider;
+    use crate::api::providers::Unihan;
+    use crate::models::ComponentTable;
+    use crate::models::UnihanRecord;
+    use std::env;
+
+    #[test]
+    fn should_write_misses_of_import_stroke_order() {
+        let input_path = env::temp_dir().join("refined-cedict-json-import.txt");
+        let output_path = env::temp_dir().join("refined-cedict-json-import.partial.txt");
+        let retry_path = env::temp_dir().join("refined-cedict-json-import.retry.txt");
+        fs::write(&input_path, "要\nx\n").unwrap();
+
+        let mut records = HashMap::new();
+        records.insert(
+            "要".to_string(),
+            UnihanRecord {
+                total_strokes: Some(9),
+                ..UnihanRecord::default()
+            },
+        );
+        let providers: Vec<(String, Box<dyn StrokeCountProvider>)> =
+            vec![("unihan".to_string(), Box::new(Unihan { records }))];
+        let mut resolver =
+            StrokeCountResolver::new(providers, ComponentTable::default(), HashMap::new());
+        let mut checkpoint = Checkpoint::open(&output_path, &retry_path, false).unwrap();
+        let mut conflicts: Vec<u8> = Vec::new();
+        let mut misses: Vec<u8> = Vec::new();
+
+        let miss_count = import_stroke_order(
+            &input_path,
+            &mut resolver,
+            &mut checkpoint,
+            &mut conflicts,
+            &mut misses,
+        )
+        .unwrap();
+        drop(checkpoint);
+
+        assert_eq!(miss_count, 1);
+        assert_eq!(String::from_utf8(misses).unwrap(), "x\n");
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "要 9\n");
+
+        for file_path in &[input_path, output_path, retry_path] {
+            fs::remove_file(file_path).unwrap();
+        }
+    }
+}
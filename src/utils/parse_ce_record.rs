@@ -1,8 +1,10 @@
 use crate::models::CERecord;
+use crate::models::DEFAULT_LANGUAGE;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-pub fn parse_ce_record(line: &str, line_number: u32) -> CERecord {
+/// Spells the ü of CC-CEDICT pinyin ("lu:4") as v ("lv4").
+pub fn normalize_ce_line(line: &str) -> String {
     let mut line = line.to_string();
 
     if line.contains("lu:4") {
@@ -25,6 +27,12 @@ pub fn parse_ce_record(line: &str, line_number: u32) -> CERecord {
         line = line.replace("nu:e4", "nve4");
     }
 
+    line
+}
+
+pub fn parse_ce_record(line: &str, line_number: u32) -> CERecord {
+    let line = normalize_ce_line(line);
+
     lazy_static! {
         static ref REGEX: Regex = Regex::new(
             r"(?P<traditional>.*?)\s(?P<simplified>.*?)\s\[(?P<pinyin>.*?)\]\s/(?P<meanings>.*)/",
@@ -55,6 +63,7 @@ pub fn parse_ce_record(line: &str, line_number: u32) -> CERecord {
         traditional: traditional,
         wade_giles_pinyin: wade_giles_pinyin,
         meanings: meanings,
+        language: DEFAULT_LANGUAGE.to_string(),
     }
 }
 